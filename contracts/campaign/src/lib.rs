@@ -13,6 +13,12 @@ pub enum DataKey {
     CampaignCount = 3,
 }
 
+#[contracttype]
+#[derive(Clone)]
+pub struct ContractInitializedEvent {
+    pub admin: Address,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct CampaignRegisteredEvent {
@@ -48,6 +54,10 @@ impl CampaignContract {
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::Initialized, &true);
         env.storage().instance().set(&DataKey::CampaignCount, &0_u64);
+        env.events().publish(
+            (Symbol::new(&env, "contract_initialized"),),
+            ContractInitializedEvent { admin },
+        );
     }
 
     /// Pause the contract, blocking all state-changing operations.
@@ -194,9 +204,31 @@ impl CampaignContract {
 
 #[cfg(test)]
 mod test {
+    extern crate std;
+
     use super::*;
+    use shared::testutils::assert_emitted_once;
     use soroban_sdk::{testutils::Address as _, Env};
 
+    #[test]
+    fn initialize_emits_event_once() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, CampaignContract);
+        let client = CampaignContractClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+
+        client.initialize(&admin);
+        assert!(client.try_initialize(&admin).is_err());
+
+        assert_emitted_once(
+            &env,
+            &contract_id,
+            "contract_initialized",
+            ContractInitializedEvent { admin },
+        );
+    }
+
     #[test]
     fn campaign_admin_and_status_flow() {
         let env = Env::default();
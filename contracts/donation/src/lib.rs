@@ -21,6 +21,13 @@ pub enum DataKey {
     Initialized = 5,
 }
 
+#[contracttype]
+#[derive(Clone)]
+pub struct ContractInitializedEvent {
+    pub admin: Address,
+    pub campaign_contract: Address,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct DonationMadeEvent {
@@ -44,6 +51,13 @@ impl DonationContract {
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::CampaignContract, &campaign_contract);
         env.storage().instance().set(&DataKey::Initialized, &true);
+        env.events().publish(
+            (Symbol::new(&env, "contract_initialized"),),
+            ContractInitializedEvent {
+                admin,
+                campaign_contract,
+            },
+        );
     }
 
     /// Pause the contract, blocking all state-changing operations.
@@ -206,8 +220,76 @@ impl DonationContract {
 
 #[cfg(test)]
 mod test {
+    extern crate std;
+
     use super::*;
-    use soroban_sdk::{testutils::Address as _, Env};
+    use shared::testutils::{advance_ledgers, advance_time, assert_emitted_once, LEDGER_SECONDS};
+    use soroban_sdk::{testutils::Address as _, token::StellarAssetClient, Env};
+
+    /// Stands in for the campaign contract: every campaign is active.
+    #[contract]
+    pub struct ActiveCampaigns;
+
+    #[contractimpl]
+    impl ActiveCampaigns {
+        pub fn get_campaign(env: Env, campaign_id: u64) -> Option<Campaign> {
+            Some(Campaign {
+                id: campaign_id,
+                owner: env.current_contract_address(),
+                goal: 1_000_000,
+                raised: 0,
+                status: CampaignStatus::Active,
+                deadline: 0,
+            })
+        }
+
+        pub fn update_raised(_env: Env, _campaign_id: u64, _amount: i128) {}
+    }
+
+    #[test]
+    fn donations_record_ledger_time() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, DonationContract);
+        let client = DonationContractClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let campaign_contract = env.register_contract(None, ActiveCampaigns);
+        let token = env.register_stellar_asset_contract(admin.clone());
+        StellarAssetClient::new(&env, &token).mint(&donor, &1_000);
+
+        client.initialize(&admin, &campaign_contract);
+        client.donate(&donor, &7_u64, &100_i128, &token, &false, &None);
+        advance_time(&env, 3_600);
+        client.donate(&donor, &7_u64, &100_i128, &token, &false, &None);
+        advance_ledgers(&env, 12);
+        client.donate(&donor, &7_u64, &100_i128, &token, &false, &None);
+
+        let donations = client.get_donations_for_campaign(&7_u64);
+        let first = donations.get(0).unwrap().timestamp;
+        assert_eq!(donations.get(1).unwrap().timestamp, first + 3_600);
+        assert_eq!(donations.get(2).unwrap().timestamp, first + 3_600 + 12 * LEDGER_SECONDS);
+    }
+
+    #[test]
+    fn initialize_emits_event_once() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, DonationContract);
+        let client = DonationContractClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        let campaign_contract = Address::generate(&env);
+
+        client.initialize(&admin, &campaign_contract);
+        assert!(client.try_initialize(&admin, &campaign_contract).is_err());
+
+        assert_emitted_once(
+            &env,
+            &contract_id,
+            "contract_initialized",
+            ContractInitializedEvent { admin, campaign_contract },
+        );
+    }
 
     #[test]
     fn donation_flow_records_history_and_total() {
@@ -1,4 +1,6 @@
 #![no_std]
 
 pub mod pause;
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
 pub mod types;
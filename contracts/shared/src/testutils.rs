@@ -0,0 +1,70 @@
+//! Test helpers shared by the contracts: ledger-time manipulation and event
+//! filtering.
+//!
+//! Enabled with the `testutils` feature, e.g. in a contract's dev-dependencies:
+//! `shared = { path = "../shared", features = ["testutils"] }`.
+
+use soroban_sdk::testutils::{Events, Ledger};
+use soroban_sdk::{vec, Address, Env, IntoVal, Symbol, Val, Vec};
+
+/// Average ledger close time assumed when converting seconds to ledgers.
+pub const LEDGER_SECONDS: u64 = 5;
+
+/// Move the ledger clock forward by `secs`, advancing the sequence number by
+/// the matching number of ledgers so sequence- and time-based checks agree.
+pub fn advance_time(env: &Env, secs: u64) {
+    env.ledger().with_mut(|li| {
+        li.timestamp += secs;
+        li.sequence_number += (secs / LEDGER_SECONDS) as u32;
+    });
+}
+
+/// Move the ledger forward by `ledgers`, advancing the clock accordingly.
+pub fn advance_ledgers(env: &Env, ledgers: u32) {
+    advance_time(env, ledgers as u64 * LEDGER_SECONDS);
+}
+
+/// Assert that the only event published under the single-symbol topic `name`
+/// came from `contract` and carried `data`.
+pub fn assert_emitted_once(
+    env: &Env,
+    contract: &Address,
+    name: &str,
+    data: impl IntoVal<Env, Val>,
+) {
+    let topic: Vec<Val> = (Symbol::new(env, name),).into_val(env);
+    let mut emitted: Vec<(Address, Val)> = Vec::new(env);
+    for (source, topics, event) in env.events().all().iter() {
+        if topics == topic {
+            emitted.push_back((source, event));
+        }
+    }
+    assert_eq!(emitted, vec![env, (contract.clone(), data.into_val(env))]);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn advance_time_moves_timestamp_and_sequence() {
+        let env = Env::default();
+        let start = env.ledger().get();
+
+        advance_time(&env, 60);
+
+        assert_eq!(env.ledger().timestamp(), start.timestamp + 60);
+        assert_eq!(env.ledger().sequence(), start.sequence_number + 12);
+    }
+
+    #[test]
+    fn advance_ledgers_moves_clock_by_close_time() {
+        let env = Env::default();
+        let start = env.ledger().get();
+
+        advance_ledgers(&env, 3);
+
+        assert_eq!(env.ledger().sequence(), start.sequence_number + 3);
+        assert_eq!(env.ledger().timestamp(), start.timestamp + 15);
+    }
+}
@@ -20,6 +20,13 @@ pub enum DataKey {
     WithdrawnAmount(u64) = 4,
 }
 
+#[contracttype]
+#[derive(Clone)]
+pub struct ContractInitializedEvent {
+    pub admin: Address,
+    pub donation_contract: Address,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct WithdrawalRequestedEvent {
@@ -58,6 +65,10 @@ impl WithdrawalContract {
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::Initialized, &true);
         env.storage().instance().set(&DataKey::DonationContract, &donation_contract);
+        env.events().publish(
+            (Symbol::new(&env, "contract_initialized"),),
+            ContractInitializedEvent { admin, donation_contract },
+        );
     }
 
     /// Pause the contract, blocking all state-changing operations.
@@ -185,9 +196,32 @@ impl WithdrawalContract {
 
 #[cfg(test)]
 mod test {
+    extern crate std;
+
     use super::*;
+    use shared::testutils::assert_emitted_once;
     use soroban_sdk::{testutils::Address as _, Env};
 
+    #[test]
+    fn initialize_emits_event_once() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, WithdrawalContract);
+        let client = WithdrawalContractClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        let donation_contract = Address::generate(&env);
+
+        client.initialize(&admin, &donation_contract);
+        assert!(client.try_initialize(&admin, &donation_contract).is_err());
+
+        assert_emitted_once(
+            &env,
+            &contract_id,
+            "contract_initialized",
+            ContractInitializedEvent { admin, donation_contract },
+        );
+    }
+
     #[test]
     fn withdrawal_requests_and_approval_flow() {
         let env = Env::default();
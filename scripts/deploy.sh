@@ -1,15 +1,31 @@
 #!/bin/bash
 set -e
 
-NETWORK=${1:-testnet}
-ADMIN_SECRET=${2:-$STELLAR_PLATFORM_SECRET}
+CONFIRM_MAINNET=false
+ARGS=()
+for arg in "$@"; do
+  if [ "$arg" = "--confirm-mainnet" ]; then
+    CONFIRM_MAINNET=true
+  else
+    ARGS+=("$arg")
+  fi
+done
+
+NETWORK=${ARGS[0]:-testnet}
+ADMIN_SECRET=${ARGS[1]:-$STELLAR_PLATFORM_SECRET}
 
 if [ -z "$ADMIN_SECRET" ]; then
-  echo "Usage: $0 [network] [admin_secret]"
+  echo "Usage: $0 [network] [admin_secret] [--confirm-mainnet]"
   echo "Or set STELLAR_PLATFORM_SECRET environment variable."
   exit 1
 fi
 
+if [ "$NETWORK" = "mainnet" ] && [ "$CONFIRM_MAINNET" != "true" ]; then
+  echo "WARNING: refusing to deploy to mainnet, which uses real funds."
+  echo "Re-run with --confirm-mainnet if this is intended."
+  exit 1
+fi
+
 if [ "$NETWORK" = "testnet" ]; then
   RPC_URL="https://soroban-testnet.stellar.org"
   PASSPHRASE="Test SDF Network ; September 2015"
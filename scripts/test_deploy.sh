@@ -0,0 +1,35 @@
+#!/bin/bash
+# Checks that mainnet deploys are refused without explicit confirmation.
+# Runs no network or build steps: every case must stop at the guard.
+set -u
+
+cd "$(dirname "$0")/.."
+
+FAILED=0
+
+fail() {
+  echo "FAIL: $1"
+  FAILED=1
+}
+
+# deploy.sh refuses mainnet without --confirm-mainnet, before building anything.
+OUTPUT=$(STELLAR_PLATFORM_SECRET=SDUMMY ./scripts/deploy.sh mainnet 2>&1)
+STATUS=$?
+[ "$STATUS" -eq 1 ] || fail "deploy.sh mainnet exited $STATUS, expected 1"
+echo "$OUTPUT" | grep -q "refusing to deploy to mainnet" \
+  || fail "deploy.sh mainnet did not print the refusal"
+echo "$OUTPUT" | grep -q "Building contracts" \
+  && fail "deploy.sh mainnet started building before refusing"
+
+# make only confirms when CONFIRM_MAINNET is exactly 1.
+for value in "" 0 yes; do
+  make -n deploy-mainnet CONFIRM_MAINNET="$value" | grep -q -- "--confirm-mainnet" \
+    && fail "make deploy-mainnet CONFIRM_MAINNET=$value passed --confirm-mainnet"
+done
+make -n deploy-mainnet CONFIRM_MAINNET=1 | grep -q -- "--confirm-mainnet" \
+  || fail "make deploy-mainnet CONFIRM_MAINNET=1 did not pass --confirm-mainnet"
+
+if [ "$FAILED" -ne 0 ]; then
+  exit 1
+fi
+echo "deploy guard checks passed"
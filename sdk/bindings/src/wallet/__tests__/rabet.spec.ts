@@ -0,0 +1,68 @@
+import { RabetAdapter } from "../rabet";
+
+const PUBLIC_KEY = "GBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2H";
+const UNSIGNED_XDR = "AAAAAgAAAAB1bnNpZ25lZA==";
+const SIGNED_XDR = "AAAAAgAAAABzaWduZWQ=";
+
+describe("RabetAdapter", () => {
+  let rabet: { connect: jest.Mock; sign: jest.Mock; disconnect: jest.Mock };
+
+  beforeEach(() => {
+    rabet = {
+      connect: jest.fn().mockResolvedValue({ publicKey: PUBLIC_KEY }),
+      sign: jest.fn().mockResolvedValue({ xdr: SIGNED_XDR }),
+      disconnect: jest.fn().mockResolvedValue(undefined),
+    };
+    (globalThis as any).window = { rabet };
+  });
+
+  afterEach(() => {
+    delete (globalThis as any).window;
+  });
+
+  it("connects and returns the public key", async () => {
+    const adapter = new RabetAdapter();
+
+    expect(adapter.isAvailable()).toBe(true);
+    await expect(adapter.connect()).resolves.toBe(PUBLIC_KEY);
+    expect(rabet.connect).toHaveBeenCalledTimes(1);
+  });
+
+  it("signs on testnet by default", async () => {
+    const adapter = new RabetAdapter();
+
+    await expect(adapter.sign(UNSIGNED_XDR)).resolves.toBe(SIGNED_XDR);
+    expect(rabet.sign).toHaveBeenCalledWith(UNSIGNED_XDR, "testnet");
+  });
+
+  it("passes the configured network to sign", async () => {
+    const adapter = new RabetAdapter("mainnet");
+
+    await adapter.sign(UNSIGNED_XDR);
+    expect(rabet.sign).toHaveBeenCalledWith(UNSIGNED_XDR, "mainnet");
+  });
+
+  it("surfaces a rejected connection request", async () => {
+    rabet.connect.mockRejectedValue(new Error("User rejected the request."));
+    const adapter = new RabetAdapter();
+
+    await expect(adapter.connect()).rejects.toThrow("User rejected the request.");
+  });
+
+  it("surfaces a rejected signing request", async () => {
+    rabet.sign.mockRejectedValue(new Error("User rejected the request."));
+    const adapter = new RabetAdapter();
+
+    await expect(adapter.sign(UNSIGNED_XDR)).rejects.toThrow("User rejected the request.");
+  });
+
+  it("throws when the extension is missing", async () => {
+    (globalThis as any).window = {};
+    const adapter = new RabetAdapter();
+
+    expect(adapter.isAvailable()).toBe(false);
+    await expect(adapter.connect()).rejects.toThrow(
+      "Rabet extension is not installed or not available."
+    );
+  });
+});
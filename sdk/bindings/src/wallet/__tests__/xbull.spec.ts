@@ -0,0 +1,94 @@
+import { XBullAdapter } from "../xbull";
+
+const PUBLIC_KEY = "GBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2H";
+const PASSPHRASE = "Test SDF Network ; September 2015";
+const UNSIGNED_XDR = "AAAAAgAAAAB1bnNpZ25lZA==";
+const SIGNED_XDR = "AAAAAgAAAABzaWduZWQ=";
+
+describe("XBullAdapter", () => {
+  let xBullSDK: { connect: jest.Mock; getPublicKey: jest.Mock; signXDR: jest.Mock };
+
+  beforeEach(() => {
+    xBullSDK = {
+      connect: jest.fn().mockResolvedValue(true),
+      getPublicKey: jest.fn().mockResolvedValue(PUBLIC_KEY),
+      signXDR: jest.fn().mockResolvedValue(SIGNED_XDR),
+    };
+    (globalThis as any).window = { xBullSDK };
+  });
+
+  afterEach(() => {
+    delete (globalThis as any).window;
+  });
+
+  it("requests key and signing permissions on connect", async () => {
+    const adapter = new XBullAdapter(PASSPHRASE);
+
+    await expect(adapter.connect()).resolves.toBe(PUBLIC_KEY);
+    expect(xBullSDK.connect).toHaveBeenCalledWith({
+      canRequestPublicKey: true,
+      canRequestSign: true,
+    });
+  });
+
+  it("passes the network passphrase and connected key to signXDR", async () => {
+    const adapter = new XBullAdapter(PASSPHRASE);
+    await adapter.connect();
+
+    await expect(adapter.sign(UNSIGNED_XDR)).resolves.toBe(SIGNED_XDR);
+    expect(xBullSDK.signXDR).toHaveBeenCalledWith(UNSIGNED_XDR, {
+      network: PASSPHRASE,
+      publicKey: PUBLIC_KEY,
+    });
+  });
+
+  it("signs on testnet by default", async () => {
+    const adapter = new XBullAdapter();
+    await adapter.connect();
+
+    await adapter.sign(UNSIGNED_XDR);
+    expect(xBullSDK.signXDR).toHaveBeenCalledWith(UNSIGNED_XDR, {
+      network: "Test SDF Network ; September 2015",
+      publicKey: PUBLIC_KEY,
+    });
+  });
+
+  it("drops the public key after disconnect", async () => {
+    const adapter = new XBullAdapter(PASSPHRASE);
+    await adapter.connect();
+    adapter.disconnect();
+
+    await adapter.sign(UNSIGNED_XDR);
+    expect(xBullSDK.signXDR).toHaveBeenCalledWith(UNSIGNED_XDR, {
+      network: PASSPHRASE,
+      publicKey: undefined,
+    });
+  });
+
+  it("throws when the user rejects the connection", async () => {
+    xBullSDK.connect.mockResolvedValue(false);
+    const adapter = new XBullAdapter(PASSPHRASE);
+
+    await expect(adapter.connect()).rejects.toThrow("xBull connection was rejected.");
+    expect(xBullSDK.getPublicKey).not.toHaveBeenCalled();
+  });
+
+  it("surfaces a rejected signing request", async () => {
+    xBullSDK.signXDR.mockRejectedValue(new Error("User declined the transaction."));
+    const adapter = new XBullAdapter(PASSPHRASE);
+
+    await expect(adapter.sign(UNSIGNED_XDR)).rejects.toThrow(
+      "User declined the transaction."
+    );
+  });
+
+  it("throws when the extension is missing", async () => {
+    (globalThis as any).window = {};
+    const adapter = new XBullAdapter(PASSPHRASE);
+
+    expect(adapter.isAvailable()).toBe(false);
+    await expect(adapter.connect()).rejects.toThrow(
+      "xBull extension is not installed or not available."
+    );
+  });
+});
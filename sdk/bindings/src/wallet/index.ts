@@ -2,6 +2,8 @@ export type { WalletAdapter } from "./types";
 export { FreighterAdapter } from "./freighter";
 export { AlbedoAdapter } from "./albedo";
 export { LobstrAdapter } from "./lobstr";
+export { RabetAdapter } from "./rabet";
+export { XBullAdapter } from "./xbull";
 export {
   WalletAdapterRegistry,
   getDefaultAdapter,
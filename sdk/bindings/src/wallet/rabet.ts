@@ -0,0 +1,46 @@
+import type { WalletAdapter } from "./types";
+
+// Minimal subset of the Rabet extension API injected as window.rabet.
+interface RabetApi {
+  connect(): Promise<{ publicKey: string }>;
+  sign(xdr: string, network: "mainnet" | "testnet"): Promise<{ xdr: string }>;
+  disconnect?(): Promise<void>;
+}
+
+declare const window: Window & {
+  rabet?: RabetApi;
+};
+
+export class RabetAdapter implements WalletAdapter {
+  readonly name = "Rabet";
+
+  constructor(private readonly network: "mainnet" | "testnet" = "testnet") {}
+
+  isAvailable(): boolean {
+    return typeof window !== "undefined" && window.rabet !== undefined;
+  }
+
+  async connect(): Promise<string> {
+    const { publicKey } = await this.api().connect();
+    return publicKey;
+  }
+
+  async sign(xdr: string): Promise<string> {
+    const { xdr: signed } = await this.api().sign(xdr, this.network);
+    return signed;
+  }
+
+  disconnect(): void {
+    // Older Rabet builds have no disconnect; the extension keeps its own session.
+    if (this.isAvailable()) {
+      void window.rabet!.disconnect?.();
+    }
+  }
+
+  private api(): RabetApi {
+    if (!this.isAvailable()) {
+      throw new Error("Rabet extension is not installed or not available.");
+    }
+    return window.rabet!;
+  }
+}
@@ -2,6 +2,8 @@ import type { WalletAdapter } from "./types";
 import { FreighterAdapter } from "./freighter";
 import { AlbedoAdapter } from "./albedo";
 import { LobstrAdapter } from "./lobstr";
+import { RabetAdapter } from "./rabet";
+import { XBullAdapter } from "./xbull";
 
 /**
  * All supported wallet adapters.
@@ -12,6 +14,8 @@ export const WalletAdapterRegistry: readonly WalletAdapter[] = [
   new FreighterAdapter(),
   new AlbedoAdapter(),
   new LobstrAdapter(),
+  new RabetAdapter(),
+  new XBullAdapter(),
 ] as const;
 
 /**
@@ -0,0 +1,65 @@
+import type { WalletAdapter } from "./types";
+
+// Minimal subset of the xBull extension SDK injected as window.xBullSDK.
+interface XBullSdk {
+  connect(permissions: {
+    canRequestPublicKey: boolean;
+    canRequestSign: boolean;
+  }): Promise<boolean>;
+  getPublicKey(): Promise<string>;
+  signXDR(
+    xdr: string,
+    opts?: { network?: string; publicKey?: string }
+  ): Promise<string>;
+}
+
+declare const window: Window & {
+  xBullSDK?: XBullSdk;
+};
+
+// xBull signs for whatever network the extension is set to when no passphrase
+// is given, so default to testnet like the other adapters.
+const TESTNET_PASSPHRASE = "Test SDF Network ; September 2015";
+
+export class XBullAdapter implements WalletAdapter {
+  readonly name = "xBull";
+
+  private _publicKey: string | null = null;
+
+  constructor(private readonly networkPassphrase: string = TESTNET_PASSPHRASE) {}
+
+  isAvailable(): boolean {
+    return typeof window !== "undefined" && window.xBullSDK !== undefined;
+  }
+
+  async connect(): Promise<string> {
+    const sdk = this.sdk();
+    const granted = await sdk.connect({
+      canRequestPublicKey: true,
+      canRequestSign: true,
+    });
+    if (!granted) {
+      throw new Error("xBull connection was rejected.");
+    }
+    this._publicKey = await sdk.getPublicKey();
+    return this._publicKey;
+  }
+
+  async sign(xdr: string): Promise<string> {
+    return this.sdk().signXDR(xdr, {
+      network: this.networkPassphrase,
+      publicKey: this._publicKey ?? undefined,
+    });
+  }
+
+  disconnect(): void {
+    this._publicKey = null;
+  }
+
+  private sdk(): XBullSdk {
+    if (!this.isAvailable()) {
+      throw new Error("xBull extension is not installed or not available.");
+    }
+    return window.xBullSDK!;
+  }
+}
@@ -1,11 +1,71 @@
 use dotenvy::dotenv;
+use serde::{Deserialize, Serialize};
 use std::env;
+use std::fmt;
 use thiserror::Error;
 
+use crate::utils::keypair::is_valid_secret_key;
+
+pub const TESTNET_PASSPHRASE: &str = "Test SDF Network ; September 2015";
+pub const MAINNET_PASSPHRASE: &str = "Public Global Stellar Network ; September 2015";
+
+/// Network a donation targets. Prefer the named variants over a raw passphrase:
+/// a one-character typo yields a transaction no network will verify.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StellarNetwork {
+    Testnet,
+    Mainnet,
+    Custom(String),
+}
+
+impl StellarNetwork {
+    /// Resolve a `STELLAR_NETWORK` name; only the named networks are known.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "testnet" => Some(StellarNetwork::Testnet),
+            "mainnet" => Some(StellarNetwork::Mainnet),
+            _ => None,
+        }
+    }
+
+    /// Resolve a passphrase, recognising the canonical testnet and mainnet ones.
+    pub fn from_passphrase(passphrase: &str) -> Self {
+        match passphrase {
+            TESTNET_PASSPHRASE => StellarNetwork::Testnet,
+            MAINNET_PASSPHRASE => StellarNetwork::Mainnet,
+            other => StellarNetwork::Custom(other.to_string()),
+        }
+    }
+
+    pub fn passphrase(&self) -> &str {
+        match self {
+            StellarNetwork::Testnet => TESTNET_PASSPHRASE,
+            StellarNetwork::Mainnet => MAINNET_PASSPHRASE,
+            StellarNetwork::Custom(passphrase) => passphrase,
+        }
+    }
+
+    pub fn is_mainnet(&self) -> bool {
+        self.passphrase() == MAINNET_PASSPHRASE
+    }
+
+    /// Guard for anything that spends real funds: deploy, invoke and submit paths
+    /// call this with their mainnet confirmation before touching the network.
+    pub fn require_mainnet_confirmation(&self, confirmed: bool) -> Result<(), ConfigError> {
+        if self.is_mainnet() && !confirmed {
+            return Err(ConfigError::MainnetNotConfirmed);
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum ConfigError {
     #[error("Missing required environment variable: {0}")]
     MissingVar(String),
+    #[error("Refusing to target mainnet without explicit confirmation (confirm_mainnet)")]
+    MainnetNotConfirmed,
 }
 
 /// Application configuration loaded from environment variables.
@@ -37,6 +97,109 @@ impl Config {
             soroban_network_passphrase: require("SOROBAN_NETWORK_PASSPHRASE")?,
         })
     }
+
+    /// The network named by `STELLAR_NETWORK`, if it is a known one.
+    pub fn network(&self) -> Option<StellarNetwork> {
+        StellarNetwork::from_name(&self.stellar_network)
+    }
+
+    pub fn is_mainnet(&self) -> bool {
+        self.network() == Some(StellarNetwork::Mainnet)
+            || StellarNetwork::from_passphrase(&self.soroban_network_passphrase).is_mainnet()
+    }
+}
+
+/// Outcome of a single configuration check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigCheck {
+    pub name: &'static str,
+    pub passed: bool,
+    pub detail: String,
+}
+
+/// Structured result of `Config::validate`, reusable by health endpoints and CLIs alike.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigReport {
+    pub checks: Vec<ConfigCheck>,
+}
+
+impl ConfigReport {
+    pub fn is_ok(&self) -> bool {
+        self.checks.iter().all(|c| c.passed)
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &ConfigCheck> {
+        self.checks.iter().filter(|c| !c.passed)
+    }
+
+    pub fn check(&self, name: &str) -> Option<&ConfigCheck> {
+        self.checks.iter().find(|c| c.name == name)
+    }
+}
+
+impl fmt::Display for ConfigReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            let mark = if check.passed { "ok" } else { "FAIL" };
+            writeln!(f, "[{:>4}] {}: {}", mark, check.name, check.detail)?;
+        }
+        Ok(())
+    }
+}
+
+impl Config {
+    /// Run all static configuration checks without performing any I/O.
+    pub fn validate(&self) -> ConfigReport {
+        let mut checks = Vec::new();
+
+        let network = self.network();
+        let expected_passphrase = network.as_ref().map(StellarNetwork::passphrase);
+        checks.push(ConfigCheck {
+            name: "network",
+            passed: expected_passphrase.is_some(),
+            detail: format!("STELLAR_NETWORK={}", self.stellar_network),
+        });
+
+        checks.push(ConfigCheck {
+            name: "passphrase",
+            passed: expected_passphrase == Some(self.soroban_network_passphrase.as_str()),
+            detail: match expected_passphrase {
+                Some(p) if p == self.soroban_network_passphrase => "matches network".to_string(),
+                Some(p) => format!("expected \"{}\"", p),
+                None => "cannot verify passphrase for unknown network".to_string(),
+            },
+        });
+
+        let secret_ok = is_valid_secret_key(&self.stellar_platform_secret);
+        checks.push(ConfigCheck {
+            name: "platform_secret",
+            passed: secret_ok,
+            detail: if secret_ok {
+                "valid S... secret key".to_string()
+            } else {
+                "STELLAR_PLATFORM_SECRET is not a valid S... secret key".to_string()
+            },
+        });
+
+        let urls = [
+            ("horizon_url", &self.horizon_url),
+            ("soroban_rpc_url", &self.soroban_rpc_url),
+        ];
+        for (name, url) in urls {
+            let passed = url.starts_with("https://") || url.starts_with("http://");
+            checks.push(ConfigCheck {
+                name,
+                passed,
+                detail: if passed {
+                    url.clone()
+                } else {
+                    format!("not an http(s) URL: {}", url)
+                },
+            });
+        }
+
+        ConfigReport { checks }
+    }
 }
 
 #[cfg(test)]
@@ -52,4 +215,103 @@ mod tests {
         let result = Config::from_env();
         assert!(result.is_err());
     }
-}
\ No newline at end of file
+
+    fn valid_config() -> Config {
+        Config {
+            stellar_network: "testnet".to_string(),
+            stellar_platform_secret: "SCZANGBA5YHTNYVVV4C3U252E2B6P6F5T3U6MM63WBSBZATAQI3EBTQ4"
+                .to_string(),
+            horizon_url: "https://horizon-testnet.stellar.org".to_string(),
+            soroban_rpc_url: "https://soroban-testnet.stellar.org".to_string(),
+            soroban_network_passphrase: TESTNET_PASSPHRASE.to_string(),
+        }
+    }
+
+    fn failing(report: &ConfigReport) -> Vec<&'static str> {
+        report.failures().map(|c| c.name).collect()
+    }
+
+    #[test]
+    fn named_networks_use_canonical_passphrases() {
+        assert_eq!(StellarNetwork::Testnet.passphrase(), "Test SDF Network ; September 2015");
+        assert_eq!(
+            StellarNetwork::Mainnet.passphrase(),
+            "Public Global Stellar Network ; September 2015"
+        );
+        let custom = StellarNetwork::Custom("Standalone Network ; February 2017".to_string());
+        assert_eq!(custom.passphrase(), "Standalone Network ; February 2017");
+    }
+
+    #[test]
+    fn networks_resolve_by_name_and_passphrase() {
+        assert_eq!(StellarNetwork::from_name("mainnet"), Some(StellarNetwork::Mainnet));
+        assert_eq!(StellarNetwork::from_name("futurenet"), None);
+        assert_eq!(
+            StellarNetwork::from_passphrase(TESTNET_PASSPHRASE),
+            StellarNetwork::Testnet
+        );
+        assert_eq!(
+            StellarNetwork::from_passphrase("Standalone Network ; February 2017"),
+            StellarNetwork::Custom("Standalone Network ; February 2017".to_string())
+        );
+    }
+
+    #[test]
+    fn valid_config_passes_all_checks() {
+        let report = valid_config().validate();
+        assert!(report.is_ok(), "{}", report);
+    }
+
+    #[test]
+    fn mismatched_passphrase_is_flagged() {
+        let mut config = valid_config();
+        config.soroban_network_passphrase = MAINNET_PASSPHRASE.to_string();
+        assert_eq!(failing(&config.validate()), vec!["passphrase"]);
+    }
+
+    #[test]
+    fn unknown_network_fails_network_and_passphrase() {
+        let mut config = valid_config();
+        config.stellar_network = "futurenet".to_string();
+        assert_eq!(failing(&config.validate()), vec!["network", "passphrase"]);
+    }
+
+    #[test]
+    fn bad_secret_and_url_are_flagged() {
+        let mut config = valid_config();
+        config.stellar_platform_secret =
+            "GBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2H".to_string();
+        config.soroban_rpc_url = "soroban-testnet.stellar.org".to_string();
+        assert_eq!(failing(&config.validate()), vec!["platform_secret", "soroban_rpc_url"]);
+    }
+
+    #[test]
+    fn mainnet_requires_confirmation() {
+        let mut config = valid_config();
+        config.stellar_network = "mainnet".to_string();
+        config.soroban_network_passphrase = MAINNET_PASSPHRASE.to_string();
+
+        let network = config.network().unwrap();
+        assert!(matches!(
+            network.require_mainnet_confirmation(false),
+            Err(ConfigError::MainnetNotConfirmed)
+        ));
+        assert!(network.require_mainnet_confirmation(true).is_ok());
+    }
+
+    #[test]
+    fn testnet_needs_no_confirmation() {
+        let network = valid_config().network().unwrap();
+        assert!(network.require_mainnet_confirmation(false).is_ok());
+    }
+
+    #[test]
+    fn custom_mainnet_passphrase_still_needs_confirmation() {
+        let network = StellarNetwork::Custom(MAINNET_PASSPHRASE.to_string());
+        assert!(matches!(
+            network.require_mainnet_confirmation(false),
+            Err(ConfigError::MainnetNotConfirmed)
+        ));
+        assert!(StellarNetwork::Testnet.require_mainnet_confirmation(false).is_ok());
+    }
+}
@@ -21,6 +21,15 @@ pub enum StellarAidError {
     #[error("Contract error: {0}")]
     ContractError(String),
 
+    #[error("Unsupported asset: {0}")]
+    UnsupportedAsset(String),
+
+    #[error("Dust amount: {amount} stroops is below the minimum of {minimum}")]
+    DustAmount { amount: i128, minimum: i128 },
+
+    #[error("Sequence number {sequence} is exhausted; merge the account into a new one")]
+    SequenceExhausted { sequence: i64 },
+
     #[error("Network error: {0}")]
     NetworkError(#[from] reqwest::Error),
 }
@@ -49,6 +58,10 @@ impl StellarAidError {
     pub fn contract(msg: impl Into<String>) -> Self {
         Self::ContractError(msg.into())
     }
+
+    pub fn unsupported_asset(token: impl Into<String>) -> Self {
+        Self::UnsupportedAsset(token.into())
+    }
 }
 
 pub type Result<T> = std::result::Result<T, StellarAidError>;
\ No newline at end of file
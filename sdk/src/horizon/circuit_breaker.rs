@@ -0,0 +1,177 @@
+use std::sync::Mutex;
+use std::time::Duration;
+// Tokio's clock, so tests can pause and advance time instead of sleeping.
+use tokio::time::Instant;
+
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the circuit.
+    pub failure_threshold: u32,
+    /// Span, measured from the first failure of a run, within which failures
+    /// accumulate. The first failure after it elapses starts a new count.
+    pub window: Duration,
+    /// How long the circuit stays open before letting a probe request through.
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            window: Duration::from_secs(30),
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests flow normally.
+    Closed,
+    /// Requests fail fast without touching the network.
+    Open,
+    /// Cooldown elapsed; a single request probes whether Horizon recovered.
+    HalfOpen,
+}
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    failures: u32,
+    first_failure_at: Option<Instant>,
+    opened_at: Option<Instant>,
+    /// When the half-open probe was let through. A probe that never reports back
+    /// (e.g. its future was dropped) is replaced after another cooldown.
+    probe_started_at: Option<Instant>,
+}
+
+#[derive(Debug)]
+pub(crate) struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    state: Mutex<BreakerState>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(BreakerState::default()),
+        }
+    }
+
+    pub(crate) fn state(&self) -> CircuitState {
+        self.state_of(&self.state.lock().unwrap())
+    }
+
+    fn state_of(&self, state: &BreakerState) -> CircuitState {
+        match state.opened_at {
+            Some(opened) if opened.elapsed() < self.config.cooldown => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+            None => CircuitState::Closed,
+        }
+    }
+
+    /// Whether a request may go out. While half-open only one probe is admitted;
+    /// concurrent callers fail fast until it reports back.
+    pub(crate) fn allows_request(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        match self.state_of(&state) {
+            CircuitState::Closed => true,
+            CircuitState::Open => false,
+            CircuitState::HalfOpen => match state.probe_started_at {
+                Some(started) if started.elapsed() < self.config.cooldown => false,
+                _ => {
+                    state.probe_started_at = Some(Instant::now());
+                    true
+                }
+            },
+        }
+    }
+
+    pub(crate) fn record_success(&self) {
+        *self.state.lock().unwrap() = BreakerState::default();
+    }
+
+    pub(crate) fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+
+        match self.state_of(&state) {
+            // A request sent before the circuit opened failed late; it must not
+            // extend the cooldown.
+            CircuitState::Open => return,
+            CircuitState::HalfOpen => {
+                // The probe failed: stay open for another cooldown.
+                state.opened_at = Some(now);
+                state.probe_started_at = None;
+                return;
+            }
+            CircuitState::Closed => {}
+        }
+
+        match state.first_failure_at {
+            Some(first) if now.duration_since(first) <= self.config.window => state.failures += 1,
+            _ => {
+                state.failures = 1;
+                state.first_failure_at = Some(now);
+            }
+        }
+        if state.failures >= self.config.failure_threshold {
+            state.opened_at = Some(now);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::time::advance;
+
+    const COOLDOWN: Duration = Duration::from_secs(30);
+
+    fn tripped() -> CircuitBreaker {
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 1,
+            window: Duration::from_secs(60),
+            cooldown: COOLDOWN,
+        });
+        breaker.record_failure();
+        breaker
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn half_open_admits_a_single_probe() {
+        let breaker = tripped();
+        assert!(!breaker.allows_request());
+
+        advance(COOLDOWN).await;
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(breaker.allows_request());
+        assert!(!breaker.allows_request());
+
+        breaker.record_success();
+        assert!(breaker.allows_request());
+        assert!(breaker.allows_request());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn failed_probe_reopens_the_circuit() {
+        let breaker = tripped();
+        advance(COOLDOWN).await;
+        assert!(breaker.allows_request());
+
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(!breaker.allows_request());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn late_failure_does_not_extend_cooldown() {
+        let breaker = tripped();
+        advance(Duration::from_secs(20)).await;
+        // A request sent before the circuit opened reports its failure now.
+        breaker.record_failure();
+
+        advance(Duration::from_secs(10)).await;
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+    }
+}
@@ -1,11 +1,23 @@
-use reqwest::Client;
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
 use serde::Deserialize;
+use serde_json::Value;
+use std::time::Duration;
 use thiserror::Error;
+use tokio::sync::OnceCell;
+
+use crate::config::{ConfigError, StellarNetwork};
+use crate::horizon::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState};
+use crate::retry::{retry_async_within, OperationTimeout, RetryConfig, RetryError};
+use crate::utils::keypair::is_valid_public_key;
 
 #[derive(Debug, Default)]
 pub struct HorizonClient {
     client: Client,
     base_url: String,
+    breaker: Option<CircuitBreaker>,
+    retry: Option<RetryConfig>,
+    network: OnceCell<StellarNetwork>,
+    mainnet_confirmed: bool,
 }
 
 #[derive(Debug, Error)]
@@ -14,6 +26,88 @@ pub enum HorizonError {
     Http(#[from] reqwest::Error),
     #[error("Horizon API error: {0}")]
     Api(String),
+    #[error("Bad request: {0}")]
+    BadRequest(String),
+    #[error("Rate limited by Horizon (retry after {retry_after:?})")]
+    RateLimited { retry_after: Option<Duration> },
+    #[error("Horizon unavailable: circuit breaker is open")]
+    ServiceUnavailable,
+    #[error("Transaction failed: {tx_code} (operations: {op_codes:?})")]
+    TransactionFailed { tx_code: String, op_codes: Vec<String> },
+    #[error("Operation timed out after {elapsed:?} ({attempts} attempts)")]
+    Timeout {
+        elapsed: Duration,
+        attempts: u32,
+        #[source]
+        last_error: Option<Box<HorizonError>>,
+    },
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+}
+
+impl From<OperationTimeout<HorizonError>> for HorizonError {
+    fn from(timeout: OperationTimeout<HorizonError>) -> Self {
+        HorizonError::Timeout {
+            elapsed: timeout.elapsed,
+            attempts: timeout.attempts,
+            last_error: timeout.last_error.map(Box::new),
+        }
+    }
+}
+
+impl HorizonError {
+    /// Classify a non-success Horizon response body. Transaction submission
+    /// failures carry `extras.result_codes`, which are surfaced as
+    /// `TransactionFailed` so callers can match on `tx_bad_seq`, `op_no_trust`, etc.
+    pub fn from_body(body: String) -> Self {
+        let codes = serde_json::from_str::<ProblemBody>(&body)
+            .ok()
+            .and_then(|p| p.extras)
+            .and_then(|e| e.result_codes);
+        match codes {
+            Some(ResultCodes {
+                transaction: Some(tx_code),
+                operations,
+            }) => HorizonError::TransactionFailed {
+                tx_code,
+                op_codes: operations.unwrap_or_default(),
+            },
+            _ => HorizonError::Api(body),
+        }
+    }
+
+    /// How long Horizon asked us to wait before retrying, if it said.
+    pub fn suggested_retry_duration(&self) -> Option<Duration> {
+        match self {
+            HorizonError::RateLimited { retry_after } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ProblemBody {
+    #[serde(default)]
+    extras: Option<ProblemExtras>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProblemExtras {
+    #[serde(default)]
+    result_codes: Option<ResultCodes>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResultCodes {
+    #[serde(default)]
+    transaction: Option<String>,
+    #[serde(default)]
+    operations: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RootResponse {
+    network_passphrase: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -21,6 +115,10 @@ pub struct AccountResponse {
     pub id: String,
     pub sequence: String,
     pub balances: Vec<Balance>,
+    #[serde(default)]
+    pub thresholds: Thresholds,
+    #[serde(default)]
+    pub signers: Vec<Signer>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -29,6 +127,23 @@ pub struct Balance {
     pub asset_type: String,
     #[serde(default)]
     pub asset_code: Option<String>,
+    #[serde(default)]
+    pub asset_issuer: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Thresholds {
+    pub low_threshold: u8,
+    pub med_threshold: u8,
+    pub high_threshold: u8,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Signer {
+    pub key: String,
+    pub weight: u8,
+    #[serde(rename = "type")]
+    pub signer_type: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -77,24 +192,127 @@ pub struct TransactionDetail {
     pub ledger: Option<u64>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct SubmitTransactionResponse {
+    pub hash: String,
+    #[serde(default)]
+    pub ledger: Option<u64>,
+    #[serde(default)]
+    pub successful: bool,
+    #[serde(default)]
+    pub result_xdr: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CollectionPage {
+    #[serde(rename = "_embedded")]
+    embedded: CollectionEmbedded,
+    #[serde(rename = "_links", default)]
+    links: Option<CollectionLinks>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CollectionEmbedded {
+    records: Vec<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CollectionLinks {
+    #[serde(default)]
+    next: Option<Link>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Link {
+    href: String,
+}
+
+/// Cursor over a Horizon collection. Each `next_page` follows the previous
+/// page's `_links.next.href`; iteration ends at the first empty page.
+pub struct Paginator<'a> {
+    client: &'a HorizonClient,
+    next: Option<String>,
+}
+
+impl Paginator<'_> {
+    pub async fn next_page(&mut self) -> Result<Option<Vec<Value>>, HorizonError> {
+        let Some(url) = self.next.take() else {
+            return Ok(None);
+        };
+        let resp = self
+            .client
+            .execute_with_retry(|| self.client.client.get(&url))
+            .await?;
+        let page: CollectionPage = resp.json().await?;
+        if page.embedded.records.is_empty() {
+            return Ok(None);
+        }
+        self.next = page
+            .links
+            .and_then(|l| l.next)
+            .map(|link| self.client.absolute_url(&link.href));
+        Ok(Some(page.embedded.records))
+    }
+}
+
 impl HorizonClient {
     pub fn new(base_url: impl Into<String>) -> Self {
         Self {
             client: Client::new(),
             base_url: base_url.into(),
+            breaker: None,
+            retry: None,
+            network: OnceCell::new(),
+            mainnet_confirmed: false,
         }
     }
 
+    /// Retry transport failures, 5xx and 429 responses per `config`, waiting out
+    /// any `Retry-After` Horizon sends (capped at `max_delay_ms`) instead of the
+    /// computed backoff. With `operation_timeout` set, a call that has not
+    /// succeeded within the budget fails with `HorizonError::Timeout`, even
+    /// mid-request or mid-backoff.
+    pub fn with_retry(mut self, config: RetryConfig) -> Self {
+        self.retry = Some(config);
+        self
+    }
+
+    /// Fail fast with `HorizonError::ServiceUnavailable` once Horizon has failed
+    /// `failure_threshold` times in a row, until the cooldown lets a probe through.
+    pub fn with_circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.breaker = Some(CircuitBreaker::new(config));
+        self
+    }
+
+    /// Allow `submit_transaction` when this Horizon turns out to serve mainnet.
+    pub fn with_mainnet_confirmed(mut self, confirmed: bool) -> Self {
+        self.mainnet_confirmed = confirmed;
+        self
+    }
+
+    /// Current breaker state; always `Closed` when no breaker is configured.
+    pub fn circuit_state(&self) -> CircuitState {
+        self.breaker
+            .as_ref()
+            .map_or(CircuitState::Closed, CircuitBreaker::state)
+    }
+
     #[tracing::instrument(skip(self), fields(address))]
     pub async fn get_account(&self, address: &str) -> Result<AccountResponse, HorizonError> {
-        let url = format!("{}/accounts/{}", self.base_url, address);
-        let resp = self.client.get(&url).send().await?;
-        if !resp.status().is_success() {
-            return Err(HorizonError::Api(resp.text().await.unwrap_or_default()));
+        if !is_valid_public_key(address) {
+            return Err(HorizonError::BadRequest(format!("invalid account id: {}", address)));
         }
+        let url = format!("{}/accounts/{}", self.base_url, address);
+        let resp = self.execute_with_retry(|| self.client.get(&url)).await?;
         Ok(resp.json().await?)
     }
 
+    /// Current sequence number of `address`, as Horizon reports it. The donation
+    /// builder adds one to this for the next transaction.
+    pub async fn get_sequence(&self, address: &str) -> Result<String, HorizonError> {
+        Ok(self.get_account(address).await?.sequence)
+    }
+
     #[tracing::instrument(skip(self), fields(address, cursor = ?cursor))]
     pub async fn get_transactions(
         &self,
@@ -105,10 +323,7 @@ impl HorizonClient {
         if let Some(c) = cursor {
             url.push_str(&format!("&cursor={}", c));
         }
-        let resp = self.client.get(&url).send().await?;
-        if !resp.status().is_success() {
-            return Err(HorizonError::Api(resp.text().await.unwrap_or_default()));
-        }
+        let resp = self.execute_with_retry(|| self.client.get(&url)).await?;
         Ok(resp.json().await?)
     }
 
@@ -122,20 +337,516 @@ impl HorizonClient {
         if let Some(c) = cursor {
             url.push_str(&format!("&cursor={}", c));
         }
-        let resp = self.client.get(&url).send().await?;
-        if !resp.status().is_success() {
-            return Err(HorizonError::Api(resp.text().await.unwrap_or_default()));
-        }
+        let resp = self.execute_with_retry(|| self.client.get(&url)).await?;
         Ok(resp.json().await?)
     }
 
     #[tracing::instrument(skip(self), fields(hash))]
     pub async fn get_transaction(&self, hash: &str) -> Result<TransactionDetail, HorizonError> {
         let url = format!("{}/transactions/{}", self.base_url, hash);
-        let resp = self.client.get(&url).send().await?;
-        if !resp.status().is_success() {
-            return Err(HorizonError::Api(resp.text().await.unwrap_or_default()));
-        }
+        let resp = self.execute_with_retry(|| self.client.get(&url)).await?;
         Ok(resp.json().await?)
     }
+
+    /// Network this Horizon serves, read from its root resource on first use.
+    pub async fn network(&self) -> Result<StellarNetwork, HorizonError> {
+        self.network
+            .get_or_try_init(|| async {
+                let url = format!("{}/", self.base_url);
+                let resp = self.execute_with_retry(|| self.client.get(&url)).await?;
+                let root: RootResponse = resp.json().await?;
+                Ok::<_, HorizonError>(StellarNetwork::from_passphrase(&root.network_passphrase))
+            })
+            .await
+            .cloned()
+    }
+
+    /// Submit a signed envelope to `/transactions` as a form-encoded `tx=` body.
+    /// Rejections come back as `HorizonError::TransactionFailed` with result codes.
+    /// If Horizon reports mainnet, the client must be built `with_mainnet_confirmed(true)`.
+    #[tracing::instrument(skip(self, signed_xdr))]
+    pub async fn submit_transaction(
+        &self,
+        signed_xdr: &str,
+    ) -> Result<SubmitTransactionResponse, HorizonError> {
+        self.network()
+            .await?
+            .require_mainnet_confirmation(self.mainnet_confirmed)?;
+        let url = format!("{}/transactions", self.base_url);
+        let resp = self
+            .execute_with_retry(|| self.client.post(&url).form(&[("tx", signed_xdr)]))
+            .await?;
+        Ok(resp.json().await?)
+    }
+
+    /// Page through any Horizon collection, e.g. `/accounts/{id}/payments?limit=200`.
+    pub fn paginate(&self, start_path: &str) -> Paginator<'_> {
+        Paginator {
+            client: self,
+            next: Some(self.absolute_url(start_path)),
+        }
+    }
+
+    fn absolute_url(&self, path_or_url: &str) -> String {
+        if path_or_url.starts_with('/') {
+            format!("{}{}", self.base_url, path_or_url)
+        } else {
+            path_or_url.to_string()
+        }
+    }
+
+    /// Send the request `build` produces, retrying outages per `with_retry`.
+    /// Without a retry config the request is sent once.
+    async fn execute_with_retry(
+        &self,
+        build: impl Fn() -> RequestBuilder,
+    ) -> Result<Response, HorizonError> {
+        let Some(config) = &self.retry else {
+            return self.send(build()).await.and_then(|attempt| attempt);
+        };
+        let hint = HorizonError::suggested_retry_duration;
+        match retry_async_within(config, hint, || self.send(build())).await {
+            Ok(attempt) => attempt,
+            Err(RetryError::Exhausted(e)) => Err(e),
+            Err(RetryError::TimedOut(timeout)) => Err(timeout.into()),
+        }
+    }
+
+    /// Send a request once, turning non-success responses into `HorizonError`s.
+    /// Transport errors, 5xx and 429 responses are outages: they count against the
+    /// circuit breaker and come back as the outer `Err`, which is retried. Other
+    /// failures (bad input, failed transactions, an open breaker) are final and
+    /// come back as `Ok(Err(_))`.
+    async fn send(
+        &self,
+        req: RequestBuilder,
+    ) -> Result<Result<Response, HorizonError>, HorizonError> {
+        if let Some(breaker) = &self.breaker {
+            if !breaker.allows_request() {
+                return Ok(Err(HorizonError::ServiceUnavailable));
+            }
+        }
+
+        let result = req.send().await;
+        let outage = match &result {
+            Ok(resp) => {
+                resp.status().is_server_error() || resp.status() == StatusCode::TOO_MANY_REQUESTS
+            }
+            Err(_) => true,
+        };
+        if let Some(breaker) = &self.breaker {
+            if outage {
+                breaker.record_failure();
+            } else {
+                breaker.record_success();
+            }
+        }
+
+        let resp = result?;
+        if resp.status().is_success() {
+            return Ok(Ok(resp));
+        }
+        let error = Self::error_from(resp).await;
+        if outage {
+            Err(error)
+        } else {
+            Ok(Err(error))
+        }
+    }
+
+    async fn error_from(resp: Response) -> HorizonError {
+        if resp.status() == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = resp
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .map(Duration::from_secs);
+            return HorizonError::RateLimited { retry_after };
+        }
+        HorizonError::from_body(resp.text().await.unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{MAINNET_PASSPHRASE, TESTNET_PASSPHRASE};
+    use crate::test_support::{MockResponse, MockServer};
+
+    #[test]
+    fn parses_transaction_result_codes() {
+        let body = r#"{
+            "type": "https://stellar.org/horizon-errors/transaction_failed",
+            "title": "Transaction Failed",
+            "status": 400,
+            "extras": {
+                "envelope_xdr": "AAAAAgAAAAA=",
+                "result_codes": {
+                    "transaction": "tx_failed",
+                    "operations": ["op_success", "op_no_trust"]
+                },
+                "result_xdr": "AAAAAAAAAGT/////AAAAAQAAAAAAAAAB////+gAAAAA="
+            }
+        }"#;
+
+        match HorizonError::from_body(body.to_string()) {
+            HorizonError::TransactionFailed { tx_code, op_codes } => {
+                assert_eq!(tx_code, "tx_failed");
+                assert_eq!(op_codes, vec!["op_success", "op_no_trust"]);
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn tx_level_failure_without_operations() {
+        let body = r#"{"status": 400, "extras": {"result_codes": {"transaction": "tx_bad_seq"}}}"#;
+        match HorizonError::from_body(body.to_string()) {
+            HorizonError::TransactionFailed { tx_code, op_codes } => {
+                assert_eq!(tx_code, "tx_bad_seq");
+                assert!(op_codes.is_empty());
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn other_bodies_stay_generic() {
+        let body = r#"{"status": 404, "title": "Resource Missing"}"#;
+        assert!(matches!(
+            HorizonError::from_body(body.to_string()),
+            HorizonError::Api(_)
+        ));
+    }
+
+    const ACCOUNT_FIXTURE: &str = r#"{
+        "id": "GBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2H",
+        "account_id": "GBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2H",
+        "sequence": "3298742863331328",
+        "subentry_count": 1,
+        "thresholds": {"low_threshold": 1, "med_threshold": 2, "high_threshold": 3},
+        "balances": [
+            {
+                "balance": "250.0000000",
+                "limit": "922337203685.4775807",
+                "asset_type": "credit_alphanum4",
+                "asset_code": "USDC",
+                "asset_issuer": "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ"
+            },
+            {"balance": "9999.9999900", "asset_type": "native"}
+        ],
+        "signers": [
+            {
+                "weight": 1,
+                "key": "GBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2H",
+                "type": "ed25519_public_key"
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn deserializes_account_fixture() {
+        let account: AccountResponse = serde_json::from_str(ACCOUNT_FIXTURE).unwrap();
+        assert_eq!(account.sequence, "3298742863331328");
+        assert_eq!(account.thresholds.med_threshold, 2);
+        assert_eq!(account.signers.len(), 1);
+        assert_eq!(account.signers[0].weight, 1);
+        assert_eq!(account.signers[0].signer_type, "ed25519_public_key");
+
+        let usdc = &account.balances[0];
+        assert_eq!(usdc.asset_code.as_deref(), Some("USDC"));
+        assert_eq!(
+            usdc.asset_issuer.as_deref(),
+            Some("GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ")
+        );
+        assert_eq!(account.balances[1].asset_issuer, None);
+    }
+
+    #[tokio::test]
+    async fn invalid_account_id_is_rejected_without_request() {
+        let server = MockServer::start(vec![MockResponse::json(200, ACCOUNT_FIXTURE)]).await;
+        let client = HorizonClient::new(&server.url);
+
+        let err = client.get_account("GNOTANACCOUNT").await.unwrap_err();
+        assert!(matches!(err, HorizonError::BadRequest(_)));
+        assert_eq!(server.hits(), 0);
+    }
+
+    #[tokio::test]
+    async fn get_sequence_returns_fixture_sequence() {
+        let server = MockServer::start(vec![MockResponse::json(200, ACCOUNT_FIXTURE)]).await;
+        let client = HorizonClient::new(&server.url);
+
+        let account = "GBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2H";
+
+        let sequence = client.get_sequence(account).await.unwrap();
+        assert_eq!(sequence, "3298742863331328");
+        assert!(server.requests()[0].starts_with(&format!("GET /accounts/{} ", account)));
+    }
+
+    fn page(ids: &[u32], next: &str) -> MockResponse {
+        let records: Vec<Value> = ids.iter().map(|id| serde_json::json!({ "id": id })).collect();
+        MockResponse::json(
+            200,
+            serde_json::json!({
+                "_links": { "next": { "href": next } },
+                "_embedded": { "records": records }
+            })
+            .to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn paginator_follows_next_links_until_empty() {
+        let server = MockServer::start(vec![
+            page(&[1, 2], "/accounts/G/payments?cursor=2"),
+            page(&[3], "/accounts/G/payments?cursor=3"),
+            page(&[], "/accounts/G/payments?cursor=3"),
+        ])
+        .await;
+        let client = HorizonClient::new(&server.url);
+
+        let mut pager = client.paginate("/accounts/G/payments?limit=2");
+        let mut ids = Vec::new();
+        while let Some(records) = pager.next_page().await.unwrap() {
+            ids.extend(records.iter().map(|r| r["id"].as_u64().unwrap()));
+        }
+
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert_eq!(server.hits(), 3);
+        assert!(server.requests()[1].starts_with("GET /accounts/G/payments?cursor=2 "));
+        assert!(pager.next_page().await.unwrap().is_none());
+        assert_eq!(server.hits(), 3);
+    }
+
+    #[tokio::test]
+    async fn rate_limit_carries_retry_after() {
+        let server = MockServer::start(vec![
+            MockResponse::json(429, "{}").with_header("Retry-After", "3")
+        ])
+        .await;
+        let client = HorizonClient::new(&server.url);
+
+        let err = client.get_transaction("abc").await.unwrap_err();
+        assert!(matches!(err, HorizonError::RateLimited { .. }));
+        assert_eq!(err.suggested_retry_duration(), Some(Duration::from_secs(3)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retry_waits_for_retry_after() {
+        let server = MockServer::start(vec![
+            MockResponse::json(429, "{}").with_header("Retry-After", "3"),
+            MockResponse::json(200, ACCOUNT_FIXTURE),
+        ])
+        .await;
+        let client = HorizonClient::new(&server.url).with_retry(RetryConfig {
+            max_attempts: 2,
+            base_delay_ms: 10,
+            ..Default::default()
+        });
+
+        let started = tokio::time::Instant::now();
+        let account = client
+            .get_account("GBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2H")
+            .await
+            .unwrap();
+
+        assert_eq!(account.sequence, "3298742863331328");
+        // The paused clock jumps through the backoff: 3s from Retry-After, not 10ms.
+        assert!(started.elapsed() >= Duration::from_secs(3));
+        assert_eq!(server.hits(), 2);
+    }
+
+    #[tokio::test]
+    async fn final_errors_are_not_retried() {
+        let server = MockServer::start(vec![MockResponse::json(404, "{}")]).await;
+        let client = HorizonClient::new(&server.url).with_retry(RetryConfig {
+            max_attempts: 3,
+            base_delay_ms: 1,
+            ..Default::default()
+        });
+
+        assert!(matches!(client.get_transaction("missing").await, Err(HorizonError::Api(_))));
+        assert_eq!(server.hits(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn operation_timeout_cuts_off_a_hung_request() {
+        // Accepts connections but never answers.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut held = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                held.push(socket);
+            }
+        });
+        let client = HorizonClient::new(url).with_retry(RetryConfig {
+            operation_timeout: Some(Duration::from_secs(30)),
+            ..Default::default()
+        });
+
+        let started = tokio::time::Instant::now();
+        match client.get_transaction("abc").await {
+            Err(HorizonError::Timeout {
+                attempts: 1,
+                last_error: None,
+                ..
+            }) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(started.elapsed(), Duration::from_secs(30));
+    }
+
+    fn breaker_config(cooldown: Duration) -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            failure_threshold: 2,
+            window: Duration::from_secs(60),
+            cooldown,
+        }
+    }
+
+    #[tokio::test]
+    async fn breaker_opens_after_repeated_failures() {
+        let server = MockServer::start(vec![MockResponse::json(503, "{}")]).await;
+        let client = HorizonClient::new(&server.url)
+            .with_circuit_breaker(breaker_config(Duration::from_secs(60)));
+
+        assert!(matches!(client.get_transaction("a").await, Err(HorizonError::Api(_))));
+        assert!(matches!(client.get_transaction("a").await, Err(HorizonError::Api(_))));
+        assert_eq!(client.circuit_state(), CircuitState::Open);
+
+        assert!(matches!(
+            client.get_transaction("a").await,
+            Err(HorizonError::ServiceUnavailable)
+        ));
+        assert_eq!(server.hits(), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn breaker_closes_after_successful_probe() {
+        let server = MockServer::start(vec![
+            MockResponse::json(503, "{}"),
+            MockResponse::json(503, "{}"),
+            MockResponse::json(200, ACCOUNT_FIXTURE),
+        ])
+        .await;
+        let client = HorizonClient::new(&server.url)
+            .with_circuit_breaker(breaker_config(Duration::from_secs(30)));
+        let account = "GBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2H";
+
+        let _ = client.get_account(account).await;
+        let _ = client.get_account(account).await;
+        assert_eq!(client.circuit_state(), CircuitState::Open);
+
+        tokio::time::advance(Duration::from_secs(30)).await;
+        assert_eq!(client.circuit_state(), CircuitState::HalfOpen);
+        assert!(client.get_account(account).await.is_ok());
+        assert_eq!(client.circuit_state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn not_found_does_not_trip_breaker() {
+        let server = MockServer::start(vec![MockResponse::json(404, "{}")]).await;
+        let client = HorizonClient::new(&server.url)
+            .with_circuit_breaker(breaker_config(Duration::from_secs(60)));
+
+        for _ in 0..3 {
+            let _ = client.get_transaction("missing").await;
+        }
+        assert_eq!(client.circuit_state(), CircuitState::Closed);
+        assert_eq!(server.hits(), 3);
+    }
+
+    fn root(passphrase: &str) -> MockResponse {
+        MockResponse::json(200, format!(r#"{{"network_passphrase":"{}"}}"#, passphrase))
+    }
+
+    const SUBMITTED: &str =
+        r#"{"hash":"abc123","ledger":42,"successful":true,"result_xdr":"AAAA"}"#;
+
+    #[tokio::test]
+    async fn submit_posts_form_encoded_envelope() {
+        let server = MockServer::start(vec![
+            root(TESTNET_PASSPHRASE),
+            MockResponse::json(200, SUBMITTED),
+        ])
+        .await;
+        let client = HorizonClient::new(&server.url);
+
+        let resp = client.submit_transaction("AAAA+b/c=").await.unwrap();
+        assert_eq!(resp.hash, "abc123");
+        assert_eq!(resp.ledger, Some(42));
+        assert!(resp.successful);
+
+        let requests = server.requests();
+        assert!(requests[0].starts_with("GET / "));
+        let request = &requests[1];
+        assert!(request.starts_with("POST /transactions "));
+        assert!(request
+            .to_lowercase()
+            .contains("content-type: application/x-www-form-urlencoded"));
+        assert!(request.ends_with("tx=AAAA%2Bb%2Fc%3D"));
+    }
+
+    #[tokio::test]
+    async fn submit_surfaces_result_codes() {
+        let server = MockServer::start(vec![
+            root(TESTNET_PASSPHRASE),
+            MockResponse::json(
+                400,
+                r#"{"status":400,"extras":{"result_codes":{"transaction":"tx_bad_seq"}}}"#,
+            ),
+        ])
+        .await;
+        let client = HorizonClient::new(&server.url);
+
+        match client.submit_transaction("AAAA").await {
+            Err(HorizonError::TransactionFailed { tx_code, .. }) => {
+                assert_eq!(tx_code, "tx_bad_seq")
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn mainnet_submit_requires_confirmation() {
+        let server = MockServer::start(vec![
+            root(MAINNET_PASSPHRASE),
+            root(MAINNET_PASSPHRASE),
+            MockResponse::json(200, SUBMITTED),
+        ])
+        .await;
+
+        let refused = HorizonClient::new(&server.url).submit_transaction("AAAA").await;
+        assert!(matches!(
+            refused,
+            Err(HorizonError::Config(ConfigError::MainnetNotConfirmed))
+        ));
+        assert_eq!(server.hits(), 1);
+
+        let resp = HorizonClient::new(&server.url)
+            .with_mainnet_confirmed(true)
+            .submit_transaction("AAAA")
+            .await
+            .unwrap();
+        assert_eq!(resp.hash, "abc123");
+        assert_eq!(server.hits(), 3);
+    }
+
+    #[tokio::test]
+    async fn network_is_read_once() {
+        let server = MockServer::start(vec![
+            root(TESTNET_PASSPHRASE),
+            MockResponse::json(200, SUBMITTED),
+        ])
+        .await;
+        let client = HorizonClient::new(&server.url);
+
+        client.submit_transaction("AAAA").await.unwrap();
+        client.submit_transaction("AAAA").await.unwrap();
+        assert_eq!(client.network().await.unwrap(), StellarNetwork::Testnet);
+        assert_eq!(server.hits(), 3);
+    }
 }
@@ -1,2 +1,3 @@
 // Horizon module - see issue #311
+pub mod circuit_breaker;
 pub mod client;
@@ -5,5 +5,7 @@ pub mod logging;
 pub mod retry;
 pub mod setup;
 pub mod soroban;
+#[cfg(test)]
+mod test_support;
 pub mod transaction_builder;
 pub mod utils;
@@ -1,4 +1,4 @@
-use tokio::time::{sleep, Duration};
+use tokio::time::{sleep, timeout_at, Duration, Instant};
 use tracing::warn;
 
 #[derive(Debug, Clone)]
@@ -7,6 +7,10 @@ pub struct RetryConfig {
     pub base_delay_ms: u64,
     pub max_delay_ms: u64,
     pub backoff_factor: f64,
+    /// Total wall-clock budget across all attempts and backoff sleeps, enforced by
+    /// `retry_async_within`. `retry_async` and `retry_async_with_hint` bound the
+    /// operation by `max_attempts` alone.
+    pub operation_timeout: Option<Duration>,
 }
 
 impl Default for RetryConfig {
@@ -16,34 +20,139 @@ impl Default for RetryConfig {
             base_delay_ms: 200,
             max_delay_ms: 10_000,
             backoff_factor: 2.0,
+            operation_timeout: None,
         }
     }
 }
 
+/// Why `retry_async_within` gave up.
+#[derive(Debug, PartialEq)]
+pub enum RetryError<E> {
+    /// `max_attempts` ran out; carries the last attempt's error.
+    Exhausted(E),
+    /// `operation_timeout` ran out, mid-attempt or before a backoff.
+    TimedOut(OperationTimeout<E>),
+}
+
+/// A retried operation ran out of its `operation_timeout` budget.
+#[derive(Debug, PartialEq)]
+pub struct OperationTimeout<E> {
+    pub elapsed: Duration,
+    pub attempts: u32,
+    /// Error of the last attempt that finished, or `None` if the first attempt
+    /// was still running when the budget ran out.
+    pub last_error: Option<E>,
+}
+
 pub async fn retry_async<F, Fut, T, E>(config: &RetryConfig, f: F) -> Result<T, E>
 where
     F: Fn() -> Fut,
     Fut: std::future::Future<Output = Result<T, E>>,
 {
+    retry_async_with_hint(config, |_: &E| None, f).await
+}
+
+/// Like `retry_async`, but when `hint` returns a delay for the error (e.g. a
+/// `Retry-After` from a rate-limited response) that delay is used instead of
+/// the computed backoff, capped at `max_delay_ms`.
+pub async fn retry_async_with_hint<F, Fut, T, E, H>(
+    config: &RetryConfig,
+    hint: H,
+    f: F,
+) -> Result<T, E>
+where
+    F: Fn() -> Fut,
+    Fut: std::future::Future<Output = Result<T, E>>,
+    H: Fn(&E) -> Option<Duration>,
+{
+    match retry_loop(config, None, hint, f).await {
+        Ok(val) => Ok(val),
+        Err(RetryError::Exhausted(e)) => Err(e),
+        Err(RetryError::TimedOut(_)) => unreachable!("no deadline was set"),
+    }
+}
+
+/// Like `retry_async_with_hint`, but also bounded by `operation_timeout`: each
+/// attempt is cut off when the budget runs out, and a backoff that would sleep
+/// past it ends the operation at once instead of waiting out a budget it
+/// cannot meet.
+pub async fn retry_async_within<F, Fut, T, E, H>(
+    config: &RetryConfig,
+    hint: H,
+    f: F,
+) -> Result<T, RetryError<E>>
+where
+    F: Fn() -> Fut,
+    Fut: std::future::Future<Output = Result<T, E>>,
+    H: Fn(&E) -> Option<Duration>,
+{
+    let deadline = config.operation_timeout.map(|budget| Instant::now() + budget);
+    retry_loop(config, deadline, hint, f).await
+}
+
+async fn retry_loop<F, Fut, T, E, H>(
+    config: &RetryConfig,
+    deadline: Option<Instant>,
+    hint: H,
+    f: F,
+) -> Result<T, RetryError<E>>
+where
+    F: Fn() -> Fut,
+    Fut: std::future::Future<Output = Result<T, E>>,
+    H: Fn(&E) -> Option<Duration>,
+{
+    let started = Instant::now();
+    let timed_out = |attempts, last_error| {
+        let elapsed = started.elapsed();
+        warn!(
+            attempts = attempts,
+            elapsed_ms = elapsed.as_millis() as u64,
+            "retry budget exhausted"
+        );
+        RetryError::TimedOut(OperationTimeout {
+            elapsed,
+            attempts,
+            last_error,
+        })
+    };
+
     let mut attempt = 0u32;
+    let mut last_error = None;
     loop {
         attempt += 1;
-        match f().await {
+        let outcome = match deadline {
+            Some(deadline) => match timeout_at(deadline, f()).await {
+                Ok(outcome) => outcome,
+                Err(_) => return Err(timed_out(attempt, last_error)),
+            },
+            None => f().await,
+        };
+        let e = match outcome {
             Ok(val) => return Ok(val),
-            Err(e) => {
-                if attempt >= config.max_attempts {
-                    return Err(e);
-                }
-                let delay = calculate_delay(config, attempt);
-                warn!(
-                    attempt = attempt,
-                    max_attempts = config.max_attempts,
-                    delay_ms = delay,
-                    "retryable operation failed, retrying"
-                );
-                sleep(Duration::from_millis(delay)).await;
-            }
+            Err(e) => e,
+        };
+        if attempt >= config.max_attempts {
+            return Err(RetryError::Exhausted(e));
         }
+        let delay = Duration::from_millis(next_delay(config, attempt, hint(&e)));
+        if deadline.is_some_and(|deadline| Instant::now() + delay >= deadline) {
+            return Err(timed_out(attempt, Some(e)));
+        }
+        warn!(
+            attempt = attempt,
+            max_attempts = config.max_attempts,
+            delay_ms = delay.as_millis() as u64,
+            "retryable operation failed, retrying"
+        );
+        sleep(delay).await;
+        last_error = Some(e);
+    }
+}
+
+fn next_delay(config: &RetryConfig, attempt: u32, hint: Option<Duration>) -> u64 {
+    match hint {
+        Some(hint) => (hint.as_millis() as u64).min(config.max_delay_ms),
+        None => calculate_delay(config, attempt),
     }
 }
 
@@ -104,6 +213,77 @@ mod tests {
         assert_eq!(counter.load(Ordering::SeqCst), 3);
     }
 
+    #[tokio::test(start_paused = true)]
+    async fn retry_loop_cut_short_by_budget() {
+        let config = RetryConfig {
+            max_attempts: 10,
+            base_delay_ms: 100,
+            backoff_factor: 1.0,
+            operation_timeout: Some(Duration::from_millis(250)),
+            ..Default::default()
+        };
+        let counter = AtomicU32::new(0);
+        let started = Instant::now();
+        let result = retry_async_within(&config, |_: &&str| None, || async {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>("transient")
+        })
+        .await;
+
+        // Two 100ms sleeps fit the budget; a third would overrun it.
+        assert_eq!(
+            result.unwrap_err(),
+            RetryError::TimedOut(OperationTimeout {
+                elapsed: Duration::from_millis(200),
+                attempts: 3,
+                last_error: Some("transient"),
+            })
+        );
+        assert_eq!(counter.load(Ordering::SeqCst), 3);
+        assert_eq!(started.elapsed(), Duration::from_millis(200));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn slow_attempt_is_cut_off_at_the_deadline() {
+        let config = RetryConfig {
+            max_attempts: 10,
+            base_delay_ms: 100,
+            operation_timeout: Some(Duration::from_secs(5)),
+            ..Default::default()
+        };
+        let counter = AtomicU32::new(0);
+        let started = Instant::now();
+        let result = retry_async_within(&config, |_: &&str| None, || async {
+            if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                return Err("transient");
+            }
+            sleep(Duration::from_secs(60)).await;
+            Ok(())
+        })
+        .await;
+
+        let Err(RetryError::TimedOut(timeout)) = result else {
+            panic!("expected a timeout, got {:?}", result);
+        };
+        assert_eq!(timeout.attempts, 2);
+        assert_eq!(timeout.last_error, Some("transient"));
+        assert_eq!(started.elapsed(), Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn unbounded_retry_exhausts_attempts() {
+        let config = RetryConfig {
+            max_attempts: 2,
+            base_delay_ms: 1,
+            ..Default::default()
+        };
+        let result = retry_async_within(&config, |_: &&str| None, || async {
+            Err::<(), _>("always fails")
+        })
+        .await;
+        assert_eq!(result.unwrap_err(), RetryError::Exhausted("always fails"));
+    }
+
     #[test]
     fn delay_calculation_backs_off() {
         let config = RetryConfig {
@@ -131,4 +311,24 @@ mod tests {
         assert_eq!(calculate_delay(&config, 3), 2500);
         assert_eq!(calculate_delay(&config, 4), 2500);
     }
+
+    #[test]
+    fn hint_overrides_computed_backoff() {
+        let config = RetryConfig {
+            base_delay_ms: 100,
+            ..Default::default()
+        };
+        assert_eq!(next_delay(&config, 1, None), 100);
+        assert_eq!(next_delay(&config, 1, Some(Duration::from_secs(3))), 3_000);
+    }
+
+    #[test]
+    fn hint_is_capped_at_max_delay() {
+        let config = RetryConfig {
+            max_delay_ms: 5_000,
+            ..Default::default()
+        };
+        // A hostile or misconfigured Retry-After must not stall the caller for an hour.
+        assert_eq!(next_delay(&config, 1, Some(Duration::from_secs(3_600))), 5_000);
+    }
 }
@@ -0,0 +1,235 @@
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+use thiserror::Error;
+use tracing::{info, warn};
+
+use crate::config::{ConfigError, StellarNetwork};
+use crate::soroban::rpc_client::SorobanRpcClient;
+use crate::utils::xdr_parser::transaction_hash;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchFailure {
+    /// Position of the envelope in the submitted batch.
+    pub index: usize,
+    pub error: String,
+}
+
+/// Why a `batch_submit` run stopped before working through the batch.
+#[derive(Debug, Error)]
+pub enum BatchError {
+    /// The batch was refused before anything was submitted, e.g. an
+    /// unconfirmed mainnet batch.
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error("checkpoint I/O failed: {0}")]
+    Checkpoint(#[from] io::Error),
+}
+
+/// Outcome of a `batch_submit` run. Hashes are hex transaction hashes.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BatchSummary {
+    /// Accepted by RPC (`PENDING` or `DUPLICATE`) in this run. Acceptance is not
+    /// inclusion: poll `get_transaction_status` to learn whether each applied.
+    pub accepted: Vec<String>,
+    pub skipped: Vec<String>,
+    pub failed: Vec<BatchFailure>,
+}
+
+/// Submit signed envelopes one by one, appending each transaction hash to
+/// `checkpoint_path` once RPC accepts it. Re-running with the same checkpoint
+/// skips envelopes whose hash is already recorded, so a crashed batch can be
+/// resumed without resubmitting. A resume also skips transactions that were
+/// accepted but later failed on-ledger; callers that need them retried must
+/// check their status and drop them from the checkpoint. Only checkpoint I/O
+/// errors abort the run, besides a mainnet batch without `confirm_mainnet`,
+/// which is refused with `BatchError::Config` before anything is submitted.
+pub async fn batch_submit(
+    rpc: &SorobanRpcClient,
+    envelopes: Vec<String>,
+    network_passphrase: &str,
+    checkpoint_path: &Path,
+    confirm_mainnet: bool,
+) -> Result<BatchSummary, BatchError> {
+    StellarNetwork::from_passphrase(network_passphrase)
+        .require_mainnet_confirmation(confirm_mainnet)?;
+    let mut recorded = read_checkpoint(checkpoint_path)?;
+    let mut checkpoint = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(checkpoint_path)?;
+    let mut summary = BatchSummary::default();
+
+    for (index, xdr) in envelopes.iter().enumerate() {
+        let hash = match transaction_hash(xdr, network_passphrase) {
+            Ok(hash) => hash,
+            Err(e) => {
+                summary.failed.push(BatchFailure {
+                    index,
+                    error: e.to_string(),
+                });
+                continue;
+            }
+        };
+
+        if recorded.contains(&hash) {
+            summary.skipped.push(hash);
+            continue;
+        }
+
+        match rpc.send_transaction(xdr).await {
+            Ok(result) if matches!(result.status.as_str(), "PENDING" | "DUPLICATE") => {
+                writeln!(checkpoint, "{}", hash)?;
+                checkpoint.flush()?;
+                info!(index, hash = %hash, status = %result.status, "batch envelope accepted");
+                recorded.insert(hash.clone());
+                summary.accepted.push(hash);
+            }
+            Ok(result) => {
+                warn!(index, hash = %hash, status = %result.status, "batch envelope rejected");
+                summary.failed.push(BatchFailure {
+                    index,
+                    error: format!("submission returned status {}", result.status),
+                });
+            }
+            Err(e) => {
+                warn!(index, hash = %hash, error = %e, "batch envelope failed");
+                summary.failed.push(BatchFailure {
+                    index,
+                    error: e.to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+fn read_checkpoint(path: &Path) -> io::Result<HashSet<String>> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(contents
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(String::from)
+            .collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(HashSet::new()),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{MockResponse, MockServer};
+    use std::path::PathBuf;
+    use stellar_xdr::curr::{
+        Limits, Memo, MuxedAccount, Preconditions, SequenceNumber, Transaction,
+        TransactionEnvelope, TransactionExt, TransactionV1Envelope, Uint256, VecM, WriteXdr,
+    };
+
+    const PASSPHRASE: &str = "Test SDF Network ; September 2015";
+
+    fn envelope(seq: i64) -> String {
+        TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: Transaction {
+                source_account: MuxedAccount::Ed25519(Uint256([9u8; 32])),
+                fee: 100,
+                seq_num: SequenceNumber(seq),
+                cond: Preconditions::None,
+                memo: Memo::None,
+                operations: VecM::default(),
+                ext: TransactionExt::V0,
+            },
+            signatures: VecM::default(),
+        })
+        .to_xdr_base64(Limits::none())
+        .unwrap()
+    }
+
+    fn checkpoint_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "stellaraid-batch-{}-{}.log",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn accepted() -> MockResponse {
+        MockResponse::json(
+            200,
+            r#"{"jsonrpc":"2.0","id":1,"result":{"hash":"x","status":"PENDING"}}"#,
+        )
+    }
+
+    fn rpc_failure() -> MockResponse {
+        MockResponse::json(
+            200,
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32603,"message":"node unavailable"}}"#,
+        )
+    }
+
+    #[tokio::test]
+    async fn resume_skips_entries_recorded_in_checkpoint() {
+        let path = checkpoint_path("resume");
+        let batch = vec![envelope(1), envelope(2), envelope(3)];
+
+        // First run is interrupted after the first envelope is accepted.
+        let server = MockServer::start(vec![accepted(), rpc_failure()]).await;
+        let rpc = SorobanRpcClient::new(&server.url);
+        let first = batch_submit(&rpc, batch.clone(), PASSPHRASE, &path, false)
+            .await
+            .unwrap();
+        assert_eq!(first.accepted.len(), 1);
+        assert_eq!(first.failed.len(), 2);
+
+        // Resume: the accepted envelope is skipped, the rest are submitted.
+        let server = MockServer::start(vec![accepted()]).await;
+        let rpc = SorobanRpcClient::new(&server.url);
+        let second = batch_submit(&rpc, batch, PASSPHRASE, &path, false).await.unwrap();
+
+        assert_eq!(second.skipped, first.accepted);
+        assert_eq!(second.accepted.len(), 2);
+        assert!(second.failed.is_empty());
+        assert_eq!(server.hits(), 2);
+        assert_eq!(read_checkpoint(&path).unwrap().len(), 3);
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn undecodable_envelope_is_reported_not_submitted() {
+        let path = checkpoint_path("invalid");
+        let server = MockServer::start(vec![accepted()]).await;
+        let rpc = SorobanRpcClient::new(&server.url);
+
+        let batch = vec!["not-xdr".to_string(), envelope(1)];
+        let summary = batch_submit(&rpc, batch, PASSPHRASE, &path, false).await.unwrap();
+
+        assert_eq!(summary.failed.len(), 1);
+        assert_eq!(summary.failed[0].index, 0);
+        assert_eq!(summary.accepted.len(), 1);
+        assert_eq!(server.hits(), 1);
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn mainnet_batch_requires_confirmation() {
+        let path = checkpoint_path("mainnet");
+        let server = MockServer::start(vec![accepted()]).await;
+        let rpc = SorobanRpcClient::new(&server.url);
+
+        let mainnet = crate::config::MAINNET_PASSPHRASE;
+        let err = batch_submit(&rpc, vec![envelope(1)], mainnet, &path, false)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BatchError::Config(ConfigError::MainnetNotConfirmed)));
+        assert_eq!(server.hits(), 0);
+        assert!(!path.exists());
+    }
+}
@@ -1,2 +1,3 @@
 // Soroban RPC module - see issue #312
+pub mod batch;
 pub mod rpc_client;
@@ -1,5 +1,12 @@
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use stellar_xdr::curr::{
+    ContractDataDurability, Hash, LedgerKey, LedgerKeyContractData, Limits, ScAddress, ScVal,
+    WriteXdr,
+};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -10,6 +17,8 @@ pub enum RpcError {
     Rpc(String),
     #[error("Unexpected status: {0}")]
     UnexpectedStatus(String),
+    #[error("Invalid ledger key: {0}")]
+    InvalidKey(String),
 }
 
 #[derive(Debug, Deserialize)]
@@ -25,6 +34,16 @@ pub struct SendResult {
     pub status: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LedgerEntry {
+    /// Base64 `LedgerKey` XDR this entry was fetched for.
+    pub key: String,
+    /// Base64 `LedgerEntryData` XDR.
+    pub xdr: String,
+    #[serde(rename = "lastModifiedLedgerSeq")]
+    pub last_modified_ledger_seq: u32,
+}
+
 #[derive(Debug, PartialEq)]
 pub enum TransactionStatus {
     Pending,
@@ -57,9 +76,52 @@ struct TxStatusResult {
     status: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct LedgerEntriesResult {
+    #[serde(default)]
+    entries: Option<Vec<LedgerEntry>>,
+}
+
+/// Short-TTL cache of `getLedgerEntries` results, keyed by ledger key XDR
+/// (which already encodes contract + key + durability).
+struct LedgerEntryCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, LedgerEntry)>>,
+}
+
+impl LedgerEntryCache {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<LedgerEntry> {
+        let entries = self.entries.lock().ok()?;
+        entries
+            .get(key)
+            .filter(|(stored_at, _)| stored_at.elapsed() < self.ttl)
+            .map(|(_, entry)| entry.clone())
+    }
+
+    fn insert(&self, entry: LedgerEntry) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(entry.key.clone(), (Instant::now(), entry));
+        }
+    }
+
+    fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+}
+
 pub struct SorobanRpcClient {
     client: Client,
     rpc_url: String,
+    ledger_cache: Option<LedgerEntryCache>,
 }
 
 impl SorobanRpcClient {
@@ -67,9 +129,17 @@ impl SorobanRpcClient {
         Self {
             client: Client::new(),
             rpc_url: rpc_url.into(),
+            ledger_cache: None,
         }
     }
 
+    /// Enable a read-through cache for `getLedgerEntries` with the given TTL.
+    /// The cache is cleared whenever a transaction is accepted through this client.
+    pub fn with_ledger_cache(mut self, ttl: Duration) -> Self {
+        self.ledger_cache = Some(LedgerEntryCache::new(ttl));
+        self
+    }
+
     async fn call<T: for<'de> Deserialize<'de>>(
         &self,
         method: &str,
@@ -107,11 +177,70 @@ impl SorobanRpcClient {
 
     #[tracing::instrument(skip(self), fields(xdr = %xdr))]
     pub async fn send_transaction(&self, xdr: &str) -> Result<SendResult, RpcError> {
-        self.call(
-            "sendTransaction",
-            serde_json::json!({ "transaction": xdr }),
-        )
-        .await
+        let result: SendResult = self
+            .call(
+                "sendTransaction",
+                serde_json::json!({ "transaction": xdr }),
+            )
+            .await?;
+
+        // Any accepted write may touch cached contract state.
+        if matches!(result.status.as_str(), "PENDING" | "DUPLICATE") {
+            if let Some(cache) = &self.ledger_cache {
+                cache.clear();
+            }
+        }
+        Ok(result)
+    }
+
+    /// Fetch ledger entries by base64 `LedgerKey` XDR, serving fresh entries from
+    /// the cache when one is configured. Keys with no entry on-ledger are omitted.
+    #[tracing::instrument(skip(self), fields(keys = keys.len()))]
+    pub async fn get_ledger_entries(&self, keys: &[String]) -> Result<Vec<LedgerEntry>, RpcError> {
+        let cache = match &self.ledger_cache {
+            Some(cache) => cache,
+            None => return self.get_ledger_entries_uncached(keys).await,
+        };
+
+        let mut found = Vec::new();
+        let mut missing = Vec::new();
+        for key in keys {
+            match cache.get(key) {
+                Some(entry) => found.push(entry),
+                None => missing.push(key.clone()),
+            }
+        }
+
+        if !missing.is_empty() {
+            let fetched = self.get_ledger_entries_uncached(&missing).await?;
+            for entry in &fetched {
+                cache.insert(entry.clone());
+            }
+            found.extend(fetched);
+        }
+        Ok(found)
+    }
+
+    /// Fetch ledger entries straight from the RPC node, bypassing the cache.
+    #[tracing::instrument(skip(self), fields(keys = keys.len()))]
+    pub async fn get_ledger_entries_uncached(
+        &self,
+        keys: &[String],
+    ) -> Result<Vec<LedgerEntry>, RpcError> {
+        let result: LedgerEntriesResult = self
+            .call("getLedgerEntries", serde_json::json!({ "keys": keys }))
+            .await?;
+        Ok(result.entries.unwrap_or_default())
+    }
+
+    /// Read a single persistent contract data entry for `contract_id` (C...) and `key`.
+    pub async fn get_contract_data(
+        &self,
+        contract_id: &str,
+        key: ScVal,
+    ) -> Result<Option<LedgerEntry>, RpcError> {
+        let ledger_key = contract_data_key(contract_id, key)?;
+        Ok(self.get_ledger_entries(&[ledger_key]).await?.into_iter().next())
     }
 
     #[tracing::instrument(skip(self), fields(hash))]
@@ -128,4 +257,95 @@ impl SorobanRpcClient {
             other => return Err(RpcError::UnexpectedStatus(other.to_string())),
         })
     }
-}
\ No newline at end of file
+}
+
+/// Encode the base64 `LedgerKey` for a persistent contract data entry.
+pub fn contract_data_key(contract_id: &str, key: ScVal) -> Result<String, RpcError> {
+    let contract = stellar_strkey::Contract::from_string(contract_id)
+        .map_err(|_| RpcError::InvalidKey(format!("invalid contract id: {}", contract_id)))?;
+    LedgerKey::ContractData(LedgerKeyContractData {
+        contract: ScAddress::Contract(Hash(contract.0)),
+        key,
+        durability: ContractDataDurability::Persistent,
+    })
+    .to_xdr_base64(Limits::none())
+    .map_err(|e| RpcError::InvalidKey(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{MockResponse, MockServer};
+
+    const CONTRACT: &str = "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE";
+
+    fn entries_body(key: &str) -> String {
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "entries": [{ "key": key, "xdr": "AAAABg==", "lastModifiedLedgerSeq": 42 }],
+                "latestLedger": 50
+            }
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn second_identical_read_is_served_from_cache() {
+        let key = contract_data_key(CONTRACT, ScVal::U32(1)).unwrap();
+        let server = MockServer::start(vec![MockResponse::json(200, entries_body(&key))]).await;
+        let client = SorobanRpcClient::new(&server.url).with_ledger_cache(Duration::from_secs(30));
+
+        let first = client.get_ledger_entries(&[key.clone()]).await.unwrap();
+        let second = client.get_ledger_entries(&[key]).await.unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(server.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn uncached_read_bypasses_cache() {
+        let key = contract_data_key(CONTRACT, ScVal::U32(1)).unwrap();
+        let server = MockServer::start(vec![MockResponse::json(200, entries_body(&key))]).await;
+        let client = SorobanRpcClient::new(&server.url).with_ledger_cache(Duration::from_secs(30));
+
+        client.get_ledger_entries(&[key.clone()]).await.unwrap();
+        client.get_ledger_entries_uncached(&[key]).await.unwrap();
+
+        assert_eq!(server.hits(), 2);
+    }
+
+    #[tokio::test]
+    async fn accepted_write_invalidates_cache() {
+        let key = contract_data_key(CONTRACT, ScVal::U32(1)).unwrap();
+        let sent = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": { "hash": "abc", "status": "PENDING" }
+        })
+        .to_string();
+        let server = MockServer::start(vec![
+            MockResponse::json(200, entries_body(&key)),
+            MockResponse::json(200, sent),
+            MockResponse::json(200, entries_body(&key)),
+        ])
+        .await;
+        let client = SorobanRpcClient::new(&server.url).with_ledger_cache(Duration::from_secs(30));
+
+        client.get_ledger_entries(&[key.clone()]).await.unwrap();
+        client.send_transaction("AAAA").await.unwrap();
+        client.get_ledger_entries(&[key]).await.unwrap();
+
+        assert_eq!(server.hits(), 3);
+    }
+
+    #[test]
+    fn contract_data_key_rejects_account_address() {
+        let result = contract_data_key(
+            "GBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2H",
+            ScVal::U32(1),
+        );
+        assert!(matches!(result, Err(RpcError::InvalidKey(_))));
+    }
+}
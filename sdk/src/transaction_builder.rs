@@ -1,15 +1,96 @@
+use crate::config::StellarNetwork;
 use crate::errors::{Result, StellarAidError};
 use crate::horizon::client::HorizonClient;
+use crate::retry::RetryConfig;
 use crate::soroban::rpc_client::SorobanRpcClient;
+use crate::utils::keypair::find_invalid_base32_positions;
+use crate::utils::xdr_parser::transaction_hash;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use stellar_strkey::Strkey;
+use stellar_xdr::curr::{
+    AccountId, FeeBumpTransaction, FeeBumpTransactionEnvelope, FeeBumpTransactionExt,
+    FeeBumpTransactionInnerTx, Hash, HostFunction, Int128Parts, InvokeContractArgs,
+    InvokeHostFunctionOp, Limits, Memo, MuxedAccount, MuxedAccountMed25519, Operation,
+    OperationBody, Preconditions, PublicKey, ReadXdr, ScAddress, ScString, ScSymbol, ScVal,
+    SequenceNumber, TimeBounds, TimePoint, Transaction, TransactionEnvelope, TransactionExt,
+    TransactionV1Envelope, Uint256, VecM, WriteXdr,
+};
 
-#[derive(Debug, Clone)]
+/// Network base fee per operation, in stroops.
+pub const BASE_FEE: i64 = 100;
+
+/// Decimal places of a Stellar amount expressed in stroops.
+pub const STROOP_DECIMALS: u32 = 7;
+
+/// Donations below this many stroops cost more in fees than they move.
+pub const DEFAULT_MIN_MEANINGFUL_STROOPS: i128 = BASE_FEE as i128;
+
+/// `MemoText` limit. Stellar counts encoded bytes, not characters.
+pub const MEMO_TEXT_MAX_BYTES: usize = 28;
+
+/// Bump when the inputs hashed by `DonationParams::request_fingerprint` change.
+const FINGERPRINT_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkConfig {
     pub rpc_url: String,
     pub horizon_url: String,
     pub network_passphrase: String,
 }
 
-#[derive(Debug, Clone)]
+impl NetworkConfig {
+    pub fn for_network(
+        network: &StellarNetwork,
+        rpc_url: impl Into<String>,
+        horizon_url: impl Into<String>,
+    ) -> Self {
+        Self {
+            rpc_url: rpc_url.into(),
+            horizon_url: horizon_url.into(),
+            network_passphrase: network.passphrase().to_string(),
+        }
+    }
+}
+
+/// Transaction-level memo attached to a donation envelope.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MemoSpec {
+    None,
+    /// UTF-8 text, at most `MEMO_TEXT_MAX_BYTES` once encoded.
+    Text(String),
+    Id(u64),
+    Hash([u8; 32]),
+}
+
+impl MemoSpec {
+    pub fn to_xdr(&self) -> Result<Memo> {
+        match self {
+            MemoSpec::None => Ok(Memo::None),
+            MemoSpec::Text(text) => {
+                let bytes = text.as_bytes().len();
+                if bytes > MEMO_TEXT_MAX_BYTES {
+                    return Err(StellarAidError::validation(format!(
+                        "memo is {} bytes UTF-8 encoded ({} characters); the limit is {} bytes",
+                        bytes,
+                        text.chars().count(),
+                        MEMO_TEXT_MAX_BYTES
+                    )));
+                }
+                let text = text
+                    .as_bytes()
+                    .to_vec()
+                    .try_into()
+                    .map_err(|_| StellarAidError::validation("memo text is too long"))?;
+                Ok(Memo::Text(text))
+            }
+            MemoSpec::Id(id) => Ok(Memo::Id(*id)),
+            MemoSpec::Hash(hash) => Ok(Memo::Hash(Hash(*hash))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DonationParams {
     pub donor: String,
     pub campaign_id: u64,
@@ -18,6 +99,162 @@ pub struct DonationParams {
     pub anonymous: bool,
     pub memo: Option<String>,
     pub donation_contract_id: String,
+    /// Account (G... or M...) that pays the network fee via a fee-bump envelope.
+    /// The fee-bump hash covers the inner signatures, so the build result carries
+    /// a `FeeBumpQuote` for wrapping the envelope once the donor has signed.
+    #[serde(default)]
+    pub fee_sponsor: Option<String>,
+    /// Token contract ids (C...) the platform accepts. `None` accepts any token;
+    /// native XLM (`token_address: None`) is always accepted.
+    #[serde(default)]
+    pub allowed_tokens: Option<Vec<String>>,
+    /// Smallest donation accepted; `None` uses `DEFAULT_MIN_MEANINGFUL_STROOPS`.
+    #[serde(default)]
+    pub min_meaningful_stroops: Option<i128>,
+    /// Account (G... or M...) set as the source of the `donate` operation when it
+    /// must differ from the transaction source, e.g. a sponsored donation.
+    #[serde(default)]
+    pub operation_source: Option<String>,
+    /// Memo for the transaction envelope. Independent of `memo`, which is only
+    /// passed to the contract; `None` leaves the envelope without a memo.
+    #[serde(default)]
+    pub tx_memo: Option<MemoSpec>,
+    /// Earliest unix time the transaction is valid, e.g. a campaign launch.
+    #[serde(default)]
+    pub not_before_unix: Option<u64>,
+    /// Validity window in seconds, counted from `not_before_unix` or from now,
+    /// whichever is later. `None` leaves the transaction without an expiry.
+    #[serde(default)]
+    pub timeout_seconds: Option<u64>,
+}
+
+/// Output of the donation transaction builder.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildDonationTxResult {
+    /// Base64 `TransactionEnvelope` for the donor to sign.
+    pub xdr: String,
+    /// Hex SHA-256 hash of `xdr`'s transaction, for tracking it on Horizon.
+    pub tx_hash_hex: String,
+    /// Fee-bump the sponsor pays, set when `DonationParams::fee_sponsor` is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_bump: Option<FeeBumpQuote>,
+}
+
+/// Outer fee-bump for a sponsored donation. The bump can only be built after the
+/// donor signs, so the builder returns what it needs instead of an envelope.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeBumpQuote {
+    /// Account (G... or M...) paying the fee.
+    pub fee_source: String,
+    /// Total outer fee in stroops; at least `fee_bump_minimum` for the envelope.
+    pub fee: i64,
+}
+
+impl FeeBumpQuote {
+    /// Wrap the donor-signed `signed_xdr` in a fee-bump paying the quoted fee.
+    /// Fails if the quote no longer covers the envelope, e.g. it was rebuilt
+    /// with a higher fee after quoting.
+    pub fn wrap(&self, signed_xdr: &str) -> Result<String> {
+        let fee_source = muxed_account(&self.fee_source, "fee sponsor")?;
+        let inner = fee_bump_inner(signed_xdr)?;
+        let minimum = fee_bump_minimum(&inner.tx, BASE_FEE);
+        if self.fee < minimum {
+            return Err(StellarAidError::validation(format!(
+                "quoted fee bump of {} stroops is below the minimum of {} for an inner fee of {}",
+                self.fee, minimum, inner.tx.fee
+            )));
+        }
+        encode_fee_bump(inner, fee_source, self.fee)
+    }
+}
+
+impl DonationParams {
+    /// Reject donations in tokens outside `allowed_tokens`.
+    pub fn check_token_allowed(&self) -> Result<()> {
+        match (&self.token_address, &self.allowed_tokens) {
+            (Some(token), Some(allowed)) if !allowed.iter().any(|t| t == token) => {
+                Err(StellarAidError::unsupported_asset(token.clone()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Reject dust donations below `min_meaningful_stroops`.
+    pub fn check_amount(&self) -> Result<()> {
+        let minimum = self
+            .min_meaningful_stroops
+            .unwrap_or(DEFAULT_MIN_MEANINGFUL_STROOPS);
+        if self.amount < minimum {
+            return Err(StellarAidError::DustAmount {
+                amount: self.amount,
+                minimum,
+            });
+        }
+        Ok(())
+    }
+
+    /// Validate every address in the request before any network call, so a
+    /// malformed strkey is reported precisely rather than as a Horizon failure.
+    pub fn check_addresses(&self) -> Result<()> {
+        muxed_account(&self.donor, "donor address")?;
+        contract_id(&self.donation_contract_id, "donation contract id")?;
+        if let Some(token) = &self.token_address {
+            contract_id(token, "token address")?;
+        }
+        if let Some(sponsor) = &self.fee_sponsor {
+            muxed_account(sponsor, "fee sponsor")?;
+        }
+        if let Some(source) = &self.operation_source {
+            muxed_account(source, "operation source")?;
+        }
+        Ok(())
+    }
+
+    /// Memo the envelope will carry: `tx_memo`, or none at all.
+    pub fn memo_spec(&self) -> MemoSpec {
+        self.tx_memo.clone().unwrap_or(MemoSpec::None)
+    }
+
+    /// Check the envelope memo is encodable; text memos must fit in 28 bytes.
+    pub fn check_memo(&self) -> Result<()> {
+        self.memo_spec().to_xdr().map(|_| ())
+    }
+
+    /// Stable SHA-256 (hex) over the inputs that determine the built transaction,
+    /// so a client can tell whether a cached build is still reusable.
+    /// Server policy (`allowed_tokens`, `min_meaningful_stroops`), the fee sponsor
+    /// (it only changes the quote, not the transaction) and RPC/Horizon URLs are
+    /// excluded; only the network passphrase matters.
+    pub fn request_fingerprint(&self, network: &NetworkConfig) -> Result<String> {
+        let canonical = serde_json::to_vec(&(
+            FINGERPRINT_VERSION,
+            &self.donor,
+            &self.donation_contract_id,
+            self.amount,
+            &self.token_address,
+            self.campaign_id,
+            &network.network_passphrase,
+            &self.memo,
+            self.memo_spec(),
+            self.not_before_unix,
+            self.timeout_seconds,
+            self.anonymous,
+            &self.operation_source,
+        ))
+        .map_err(|e| StellarAidError::validation(format!("cannot fingerprint request: {}", e)))?;
+        Ok(Sha256::digest(&canonical)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect())
+    }
+}
+
+/// Round a stroop `amount` down to an asset that keeps `decimals` decimal places.
+/// Returns the rounded amount and the discarded remainder, both in stroops.
+pub fn round_to_asset_precision(amount: i128, decimals: u32) -> (i128, i128) {
+    let step = 10i128.pow(STROOP_DECIMALS.saturating_sub(decimals));
+    let remainder = amount % step;
+    (amount - remainder, remainder)
 }
 
 pub async fn build_donate_transaction(
@@ -35,161 +272,1084 @@ pub async fn build_donate_transaction(
         anonymous: false,
         memo: None,
         donation_contract_id: donation_contract_id.to_string(),
+        fee_sponsor: None,
+        allowed_tokens: None,
+        min_meaningful_stroops: None,
+        operation_source: None,
+        tx_memo: None,
+        not_before_unix: None,
+        timeout_seconds: None,
     };
-    build_donate_transaction_full(&params, network).await
+    Ok(build_donate_transaction_full(&params, network).await?.xdr)
 }
 
 pub async fn build_donate_transaction_full(
     params: &DonationParams,
     network: &NetworkConfig,
-) -> Result<String> {
-    use soroban_sdk::xdr::{
-        AccountId, Hash, HostFunction, InvokeHostFunctionOp, Memo, MuxedAccount,
-        Operation, OperationBody, Preconditions, PublicKey, ScAddress, ScVal,
-        ScVec, SequenceNumber, TimeBounds, Transaction, TransactionEnvelope,
-        TransactionExt, Uint256, VecM, WriteXdr,
-    };
+) -> Result<BuildDonationTxResult> {
+    params.check_addresses()?;
+    params.check_token_allowed()?;
+    params.check_amount()?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    // A muxed (M...) donor keeps its sub-account id on the transaction source;
+    // Horizon and the contract only know the underlying G... account.
+    let (_, donor_key) = donor_source(&params.donor)?;
+    let donor_account = stellar_strkey::ed25519::PublicKey(donor_key).to_string();
 
-    let horizon = HorizonClient::new(&network.horizon_url);
+    // Honour Retry-After when Horizon rate-limits the account lookup.
+    let horizon = HorizonClient::new(&network.horizon_url).with_retry(RetryConfig::default());
     let rpc = SorobanRpcClient::new(&network.rpc_url);
 
-    let account = horizon
-        .get_account(&params.donor)
+    let sequence = horizon
+        .get_sequence(&donor_account)
         .await
         .map_err(|e| StellarAidError::horizon(format!("failed to fetch account: {}", e)))?;
-    let seq: u64 = account
-        .sequence
-        .parse()
-        .map_err(|_| StellarAidError::horizon("invalid sequence number"))?;
+    let seq_num = next_sequence(&sequence)?;
 
-    let donor_pk = stellar_strkey::Strkey::from_string(&params.donor)
-        .map_err(|_| StellarAidError::validation("invalid donor address"))?;
+    let tx = donation_transaction(params, seq_num, now)?;
+    let xdr = encode_transaction(tx.clone())?;
 
-    let source_account = match &donor_pk {
-        stellar_strkey::Strkey::PublicKeyEd25519(pk) => {
-            MuxedAccount::Ed25519(Uint256(pk.0))
-        }
-        _ => return Err(StellarAidError::validation("donor must be a G... address")),
+    let simulation = rpc
+        .simulate_transaction(&xdr)
+        .await
+        .map_err(|e| StellarAidError::soroban(format!("simulation failed: {}", e)))?;
+
+    let sim_fee = simulation
+        .cost
+        .as_ref()
+        .and_then(|c| c.get("minResourceFee").and_then(|v| v.as_u64()))
+        .unwrap_or(100_000);
+
+    let fee = u32::try_from(sim_fee + 100)
+        .map_err(|_| StellarAidError::soroban(format!("resource fee {} is too large", sim_fee)))?;
+    let tx = Transaction { fee, ..tx };
+    let fee_bump = params.fee_sponsor.as_ref().map(|sponsor| FeeBumpQuote {
+        fee_source: sponsor.clone(),
+        fee: fee_bump_minimum(&tx, BASE_FEE),
+    });
+    let xdr = encode_transaction(tx)?;
+    let tx_hash_hex = envelope_hash(&xdr, &network.network_passphrase)?;
+
+    Ok(BuildDonationTxResult {
+        xdr,
+        tx_hash_hex,
+        fee_bump,
+    })
+}
+
+/// Unsimulated transaction invoking `donate` on the donation contract, using
+/// the donor as transaction source at `seq_num`. `now` anchors the time bounds.
+fn donation_transaction(params: &DonationParams, seq_num: i64, now: u64) -> Result<Transaction> {
+    let memo = params.memo_spec().to_xdr()?;
+    let bounds = time_bounds(params.not_before_unix, params.timeout_seconds, now)?;
+    let op_source = params
+        .operation_source
+        .as_deref()
+        .map(|addr| muxed_account(addr, "operation source"))
+        .transpose()?;
+    let contract_address = ScAddress::Contract(Hash(contract_id(
+        &params.donation_contract_id,
+        "donation contract id",
+    )?));
+    let (source_account, donor_key) = donor_source(&params.donor)?;
+    let donor_addr =
+        ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(donor_key))));
+
+    let token = match &params.token_address {
+        Some(addr) => Hash(contract_id(addr, "token address")?),
+        None => Hash([0u8; 32]),
     };
+    let memo_arg = match &params.memo {
+        Some(m) => ScVal::String(ScString(
+            m.as_bytes()
+                .to_vec()
+                .try_into()
+                .map_err(|_| StellarAidError::validation("memo is too long"))?,
+        )),
+        None => ScVal::Void,
+    };
+    let args: VecM<ScVal> = vec![
+        ScVal::Address(donor_addr),
+        ScVal::U64(params.campaign_id),
+        ScVal::I128(Int128Parts {
+            hi: (params.amount >> 64) as i64,
+            lo: params.amount as u64,
+        }),
+        ScVal::Address(ScAddress::Contract(token)),
+        ScVal::Bool(params.anonymous),
+        memo_arg,
+    ]
+    .try_into()
+    .map_err(|_| StellarAidError::validation("too many contract arguments"))?;
 
-    let donor_addr = match &donor_pk {
-        stellar_strkey::Strkey::PublicKeyEd25519(pk) => {
-            ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(pk.0))))
-        }
-        _ => return Err(StellarAidError::validation("donor must be a G... address")),
+    let function_name = ScSymbol(
+        "donate"
+            .try_into()
+            .map_err(|_| StellarAidError::validation("invalid function name"))?,
+    );
+    let op = Operation {
+        source_account: op_source,
+        body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+            host_function: HostFunction::InvokeContract(InvokeContractArgs {
+                contract_address,
+                function_name,
+                args,
+            }),
+            auth: VecM::default(),
+        }),
     };
 
-    let contract_raw = stellar_strkey::Strkey::from_string(&params.donation_contract_id)
-        .map_err(|_| StellarAidError::validation("invalid contract id"))?;
+    Ok(Transaction {
+        source_account,
+        fee: 100_000,
+        seq_num: SequenceNumber(seq_num),
+        cond: Preconditions::Time(bounds),
+        memo,
+        operations: vec![op]
+            .try_into()
+            .map_err(|_| StellarAidError::validation("too many operations"))?,
+        ext: TransactionExt::V0,
+    })
+}
 
-    let contract_addr = match &contract_raw {
-        stellar_strkey::Strkey::ContractId(h) => ScAddress::Contract(Hash(Uint256(h.0))),
-        _ => return Err(StellarAidError::validation("expected a C... contract id")),
-    };
+/// Encode `tx` as an unsigned v1 transaction envelope.
+fn encode_transaction(tx: Transaction) -> Result<String> {
+    TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx,
+        signatures: VecM::default(),
+    })
+    .to_xdr_base64(Limits::none())
+    .map_err(|e| StellarAidError::validation(format!("XDR encoding failed: {}", e)))
+}
 
-    let sym_donate = "donate"
-        .try_into()
-        .map_err(|_| StellarAidError::validation("invalid function name"))?;
-
-    let mut params_sc = ScVec(VecM::default());
-    params_sc.0.push(donor_addr);
-    params_sc.0.push(ScVal::U64(params.campaign_id));
-    params_sc.0.push(ScVal::I128(soroban_sdk::xdr::Int128Parts {
-        lo: params.amount as u64,
-        hi: (params.amount >> 64) as u64,
-    }));
-
-    let token_val = match &params.token_address {
-        Some(addr) => {
-            let raw = stellar_strkey::Strkey::from_string(addr)
-                .map_err(|_| StellarAidError::validation("invalid token address"))?;
-            match &raw {
-                stellar_strkey::Strkey::ContractId(h) => {
-                    ScVal::Address(ScAddress::Contract(Hash(Uint256(h.0))))
-                }
-                _ => return Err(StellarAidError::validation("expected a C... contract id for token")),
-            }
+/// Wrap a donor-signed transaction envelope in a fee-bump paid by `fee_source`.
+/// The fee-bump hash covers the inner signatures, so the donor must sign before
+/// this is called. The outer fee is the smallest the network accepts when bidding
+/// at least `base_fee` per operation; see `fee_bump_minimum`.
+pub fn wrap_in_fee_bump(inner_xdr: &str, fee_source: &str, base_fee: i64) -> Result<String> {
+    let fee_source = muxed_account(fee_source, "fee source")?;
+    let inner = fee_bump_inner(inner_xdr)?;
+    let fee = fee_bump_minimum(&inner.tx, base_fee);
+    encode_fee_bump(inner, fee_source, fee)
+}
+
+/// Re-bid a stuck donation at `new_base_fee` stroops per operation, counting the
+/// fee-bump itself as an operation. The inner Soroban resource fee is carried
+/// over on top. The result's `xdr` is the fee-bump envelope wrapping the signed
+/// `inner_xdr`, for `fee_source` to sign.
+pub fn build_fee_bump_transaction(
+    inner_xdr: &str,
+    fee_source: &str,
+    new_base_fee: u32,
+    network_passphrase: &str,
+) -> Result<BuildDonationTxResult> {
+    let fee_source = muxed_account(fee_source, "fee source")?;
+    let inner = fee_bump_inner(inner_xdr)?;
+    let op_count = inner.tx.operations.len().max(1) as i64;
+    let fee = new_base_fee as i64 * (op_count + 1) + resource_fee(&inner.tx);
+    let minimum = fee_bump_minimum(&inner.tx, BASE_FEE);
+    if fee < minimum {
+        return Err(StellarAidError::validation(format!(
+            "fee bump of {} stroops is below the minimum of {} for an inner fee of {}",
+            fee, minimum, inner.tx.fee
+        )));
+    }
+
+    let xdr = encode_fee_bump(inner, fee_source, fee)?;
+    let tx_hash_hex = envelope_hash(&xdr, network_passphrase)?;
+    Ok(BuildDonationTxResult {
+        xdr,
+        tx_hash_hex,
+        fee_bump: None,
+    })
+}
+
+/// Smallest fee-bump fee the network accepts for `inner` (CAP-0015): the outer
+/// inclusion fee per operation, counting the fee-bump as one, must be at least the
+/// inner inclusion fee per operation and at least `base_fee`. The inner Soroban
+/// resource fee is not part of the bid and is carried over unchanged.
+fn fee_bump_minimum(inner: &Transaction, base_fee: i64) -> i64 {
+    let op_count = inner.operations.len().max(1) as i64;
+    let resource_fee = resource_fee(inner);
+    let inclusion_fee = inner.fee as i64 - resource_fee;
+    // ceil(inclusion_fee / op_count) * (op_count + 1), without losing precision.
+    let rate_floor = (inclusion_fee * (op_count + 1) + op_count - 1) / op_count;
+    resource_fee + rate_floor.max(base_fee * (op_count + 1))
+}
+
+/// Soroban resource fee declared by `tx`, zero for classic transactions.
+fn resource_fee(tx: &Transaction) -> i64 {
+    match &tx.ext {
+        TransactionExt::V1(data) => data.resource_fee,
+        TransactionExt::V0 => 0,
+    }
+}
+
+fn envelope_hash(xdr: &str, network_passphrase: &str) -> Result<String> {
+    transaction_hash(xdr, network_passphrase)
+        .map_err(|e| StellarAidError::validation(format!("failed to hash transaction: {}", e)))
+}
+
+fn fee_bump_inner(inner_xdr: &str) -> Result<TransactionV1Envelope> {
+    match TransactionEnvelope::from_xdr_base64(inner_xdr, Limits::none())
+        .map_err(|e| StellarAidError::validation(format!("invalid inner transaction XDR: {}", e)))?
+    {
+        TransactionEnvelope::Tx(env) => Ok(env),
+        _ => Err(StellarAidError::validation(
+            "fee-bump inner transaction must be a v1 transaction envelope",
+        )),
+    }
+}
+
+fn encode_fee_bump(
+    inner: TransactionV1Envelope,
+    fee_source: MuxedAccount,
+    fee: i64,
+) -> Result<String> {
+    let envelope = TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope {
+        tx: FeeBumpTransaction {
+            fee_source,
+            fee,
+            inner_tx: FeeBumpTransactionInnerTx::Tx(inner),
+            ext: FeeBumpTransactionExt::V0,
+        },
+        signatures: VecM::default(),
+    });
+    envelope
+        .to_xdr_base64(Limits::none())
+        .map_err(|e| StellarAidError::validation(format!("XDR encoding failed: {}", e)))
+}
+
+/// Set the source account of operation `op_index` in a v1 transaction envelope,
+/// leaving the transaction source untouched. Existing signatures are dropped
+/// since they no longer cover the modified transaction.
+pub fn set_operation_source(xdr: &str, op_index: usize, source: &str) -> Result<String> {
+    let source = muxed_account(source, "operation source")?;
+
+    let mut env = match TransactionEnvelope::from_xdr_base64(xdr, Limits::none())
+        .map_err(|e| StellarAidError::validation(format!("invalid transaction XDR: {}", e)))?
+    {
+        TransactionEnvelope::Tx(env) => env,
+        _ => {
+            return Err(StellarAidError::validation(
+                "operation sources can only be set on a v1 transaction envelope",
+            ))
         }
-        None => ScVal::Address(ScAddress::Contract(Hash(Uint256([0u8; 32])))),
     };
-    params_sc.0.push(token_val);
 
-    params_sc.0.push(ScVal::Bool(params.anonymous));
+    let mut ops = env.tx.operations.to_vec();
+    let op = ops.get_mut(op_index).ok_or_else(|| {
+        StellarAidError::validation(format!("transaction has no operation {}", op_index))
+    })?;
+    op.source_account = Some(source);
+    env.tx.operations = ops
+        .try_into()
+        .map_err(|_| StellarAidError::validation("too many operations"))?;
+    env.signatures = VecM::default();
 
-    let memo_val = match &params.memo {
-        Some(m) => {
-            let mut chars = VecM::default();
-            for b in m.bytes() {
-                chars.push(b as i64);
-            }
-            ScVal::Vec(Some(ScVec(chars)))
-        }
-        None => ScVal::Void,
+    TransactionEnvelope::Tx(env)
+        .to_xdr_base64(Limits::none())
+        .map_err(|e| StellarAidError::validation(format!("XDR encoding failed: {}", e)))
+}
+
+/// Sequence number for the donor's next transaction. Horizon reports the current
+/// sequence as a string; it must be a non-negative i64 with room for one more.
+fn next_sequence(current: &str) -> Result<i64> {
+    let sequence: i64 = current
+        .parse()
+        .ok()
+        .filter(|s| *s >= 0)
+        .ok_or_else(|| StellarAidError::horizon(format!("invalid sequence number {:?}", current)))?;
+    sequence
+        .checked_add(1)
+        .ok_or(StellarAidError::SequenceExhausted { sequence })
+}
+
+/// Transaction time bounds. `min_time` is `not_before` (0 when unset); `max_time`
+/// is `timeout` seconds after the later of `not_before` and `now` (0, meaning no
+/// expiry, when unset).
+fn time_bounds(not_before: Option<u64>, timeout: Option<u64>, now: u64) -> Result<TimeBounds> {
+    let min_time = not_before.unwrap_or(0);
+    let max_time = match timeout {
+        Some(0) => return Err(StellarAidError::validation("timeout_seconds must be positive")),
+        Some(secs) => min_time
+            .max(now)
+            .checked_add(secs)
+            .ok_or_else(|| StellarAidError::validation("timeout_seconds is too large"))?,
+        None => 0,
     };
-    params_sc.0.push(memo_val);
+    Ok(TimeBounds {
+        min_time: TimePoint(min_time),
+        max_time: TimePoint(max_time),
+    })
+}
 
-    let host_fn = HostFunction::InvokeHostFunction(InvokeHostFunctionOp {
-        contract_id: contract_addr,
-        function_name: sym_donate,
-        parameters: params_sc,
-        auth: VecM::default(),
-    });
+/// Resolve the donor into the transaction source and the ed25519 key of the
+/// underlying account. M... donors keep their sub-account id on the source.
+fn donor_source(donor: &str) -> Result<(MuxedAccount, [u8; 32])> {
+    let source = muxed_account(donor, "donor address")?;
+    let key = match &source {
+        MuxedAccount::Ed25519(key) => key.0,
+        MuxedAccount::MuxedEd25519(m) => m.ed25519.0,
+    };
+    Ok((source, key))
+}
 
-    let op = Operation {
-        source_account: None,
-        body: OperationBody::InvokeHostFunction(host_fn),
+/// Strkey version bytes; each selects the leading character of the address.
+const ACCOUNT_VERSION: u8 = 6 << 3;
+const MUXED_ACCOUNT_VERSION: u8 = 12 << 3;
+const CONTRACT_VERSION: u8 = 2 << 3;
+
+/// Parse a G... or M... address into an XDR `MuxedAccount`.
+fn muxed_account(address: &str, what: &str) -> Result<MuxedAccount> {
+    match Strkey::from_string(address) {
+        Ok(Strkey::PublicKeyEd25519(pk)) => Ok(MuxedAccount::Ed25519(Uint256(pk.0))),
+        Ok(Strkey::MuxedAccountEd25519(m)) => Ok(MuxedAccount::MuxedEd25519(MuxedAccountMed25519 {
+            id: m.id,
+            ed25519: Uint256(m.ed25519),
+        })),
+        Ok(_) => Err(StellarAidError::validation(format!(
+            "invalid {}: expected a G... or M... address",
+            what
+        ))),
+        Err(_) => Err(strkey_error(address, what, &[ACCOUNT_VERSION, MUXED_ACCOUNT_VERSION])),
+    }
+}
+
+/// Parse a C... contract id into its raw 32-byte hash.
+fn contract_id(address: &str, what: &str) -> Result<[u8; 32]> {
+    match Strkey::from_string(address) {
+        Ok(Strkey::Contract(c)) => Ok(c.0),
+        Ok(_) => Err(StellarAidError::validation(format!(
+            "invalid {}: expected a C... contract id",
+            what
+        ))),
+        Err(_) => Err(strkey_error(address, what, &[CONTRACT_VERSION])),
+    }
+}
+
+/// Explain why `address` failed to decode as a strkey of one of the `expected`
+/// version bytes.
+fn strkey_error(address: &str, what: &str, expected: &[u8]) -> StellarAidError {
+    let invalid = find_invalid_base32_positions(address);
+    let reason = if !invalid.is_empty() {
+        format!("invalid base32 characters at positions {:?}", invalid)
+    } else if !strkey_version(address).is_some_and(|v| expected.contains(&v)) {
+        let prefixes: Vec<String> = expected
+            .iter()
+            .map(|v| format!("{}...", BASE32_ALPHABET[(v >> 3) as usize] as char))
+            .collect();
+        format!("unexpected key type, expected {}", prefixes.join(" or "))
+    } else if !matches!(address.len(), 56 | 69) {
+        format!("unexpected length {}", address.len())
+    } else {
+        "checksum mismatch".to_string()
     };
+    StellarAidError::validation(format!("invalid {}: {}", what, reason))
+}
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Version byte of a strkey: the first 8 bits of its base32 payload.
+fn strkey_version(address: &str) -> Option<u8> {
+    let mut digits = address
+        .bytes()
+        .map(|c| BASE32_ALPHABET.iter().position(|&a| a == c).map(|i| i as u8));
+    let first = digits.next()??;
+    let second = digits.next()??;
+    Some((first << 3) | (second >> 2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TESTNET_PASSPHRASE;
+
+    fn params() -> DonationParams {
+        DonationParams {
+            donor: "GBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2H".to_string(),
+            campaign_id: 7,
+            amount: 1_000_000_000,
+            token_address: None,
+            anonymous: false,
+            memo: Some("thanks".to_string()),
+            donation_contract_id: "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE"
+                .to_string(),
+            fee_sponsor: None,
+            allowed_tokens: None,
+            min_meaningful_stroops: None,
+            operation_source: None,
+            tx_memo: None,
+            not_before_unix: None,
+            timeout_seconds: None,
+        }
+    }
+
+    fn testnet() -> NetworkConfig {
+        NetworkConfig::for_network(
+            &StellarNetwork::Testnet,
+            "https://soroban-testnet.stellar.org",
+            "https://horizon-testnet.stellar.org",
+        )
+    }
+
+    fn decode_tx(xdr: &str) -> Transaction {
+        match TransactionEnvelope::from_xdr_base64(xdr, Limits::none()).unwrap() {
+            TransactionEnvelope::Tx(env) => env.tx,
+            other => panic!("expected a v1 envelope, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn donation_envelope_invokes_donate() {
+        let mut p = params();
+        p.timeout_seconds = Some(300);
+        let xdr = encode_transaction(donation_transaction(&p, 43, 1_700_000_000).unwrap()).unwrap();
+        let tx = decode_tx(&xdr);
+
+        let (source, donor_key) = donor_source(&p.donor).unwrap();
+        assert_eq!(tx.source_account, source);
+        assert_eq!(tx.seq_num, SequenceNumber(43));
+        assert_eq!(tx.memo, Memo::None);
+        assert_eq!(
+            tx.cond,
+            Preconditions::Time(TimeBounds {
+                min_time: TimePoint(0),
+                max_time: TimePoint(1_700_000_300),
+            })
+        );
+
+        assert_eq!(tx.operations.len(), 1);
+        let OperationBody::InvokeHostFunction(op) = &tx.operations[0].body else {
+            panic!("expected an invoke host function operation");
+        };
+        let HostFunction::InvokeContract(call) = &op.host_function else {
+            panic!("expected a contract invocation");
+        };
+        assert_eq!(
+            call.contract_address,
+            ScAddress::Contract(Hash(contract_id(&p.donation_contract_id, "contract").unwrap()))
+        );
+        assert_eq!(call.function_name.0.as_slice(), b"donate");
+        assert_eq!(call.args.len(), 6);
+        assert_eq!(
+            call.args[0],
+            ScVal::Address(ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(
+                Uint256(donor_key)
+            ))))
+        );
+        assert_eq!(call.args[1], ScVal::U64(7));
+        assert_eq!(call.args[2], ScVal::I128(Int128Parts { hi: 0, lo: 1_000_000_000 }));
+        assert_eq!(call.args[4], ScVal::Bool(false));
+        assert_eq!(
+            call.args[5],
+            ScVal::String(ScString(b"thanks".to_vec().try_into().unwrap()))
+        );
+    }
+
+    /// Mock Horizon and RPC answering one build: the donor account, then a
+    /// simulation asking for a 52_000 stroop resource fee.
+    async fn build_server() -> (crate::test_support::MockServer, NetworkConfig) {
+        use crate::test_support::{MockResponse, MockServer};
+
+        let account = serde_json::json!({
+            "id": params().donor,
+            "sequence": "3298742863331328",
+            "balances": []
+        });
+        let simulation = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": { "cost": { "minResourceFee": 52_000 } }
+        });
+        let server = MockServer::start(vec![
+            MockResponse::json(200, account.to_string()),
+            MockResponse::json(200, simulation.to_string()),
+        ])
+        .await;
+        let network = NetworkConfig {
+            rpc_url: server.url.clone(),
+            horizon_url: server.url.clone(),
+            network_passphrase: TESTNET_PASSPHRASE.to_string(),
+        };
+        (server, network)
+    }
+
+    #[tokio::test]
+    async fn full_build_applies_simulated_fee() {
+        let (server, network) = build_server().await;
+
+        let built = build_donate_transaction_full(&params(), &network).await.unwrap();
+        let tx = decode_tx(&built.xdr);
+        assert_eq!(tx.fee, 52_100);
+        assert_eq!(tx.seq_num, SequenceNumber(3_298_742_863_331_329));
+        assert_eq!(built.tx_hash_hex, transaction_hash(&built.xdr, TESTNET_PASSPHRASE).unwrap());
+        assert_eq!(built.fee_bump, None);
+        assert!(server.requests()[1].contains("simulateTransaction"));
+    }
+
+    #[tokio::test]
+    async fn sponsored_build_quotes_fee_bump() {
+        use stellar_xdr::curr::{DecoratedSignature, Signature, SignatureHint};
+
+        let (_server, network) = build_server().await;
+        let mut p = params();
+        p.fee_sponsor = Some(SPONSOR.to_string());
+
+        let built = build_donate_transaction_full(&p, &network).await.unwrap();
+        let quote = built.fee_bump.clone().expect("sponsored build carries a quote");
+        assert_eq!(quote.fee_source, SPONSOR);
+        // The 52_100 stroop bid, rebid for the donation and the fee-bump.
+        assert_eq!(quote.fee, 104_200);
+
+        let signature = DecoratedSignature {
+            hint: SignatureHint([1, 2, 3, 4]),
+            signature: Signature(vec![9u8; 64].try_into().unwrap()),
+        };
+        let signed_xdr = TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: decode_tx(&built.xdr),
+            signatures: vec![signature.clone()].try_into().unwrap(),
+        })
+        .to_xdr_base64(Limits::none())
+        .unwrap();
+
+        let outer_xdr = quote.wrap(&signed_xdr).unwrap();
+        let outer = TransactionEnvelope::from_xdr_base64(&outer_xdr, Limits::none()).unwrap();
+        let TransactionEnvelope::TxFeeBump(bump) = outer else {
+            panic!("expected a fee-bump envelope");
+        };
+        assert_eq!(bump.tx.fee_source, muxed_account(SPONSOR, "sponsor").unwrap());
+        assert_eq!(bump.tx.fee, 104_200);
+        let FeeBumpTransactionInnerTx::Tx(inner) = bump.tx.inner_tx;
+        assert_eq!(inner.tx, decode_tx(&built.xdr));
+        assert_eq!(inner.signatures.to_vec(), vec![signature]);
+    }
+
+    #[test]
+    fn underpriced_quote_is_rejected() {
+        let (_, inner_xdr) = donor_envelope();
+        let quote = FeeBumpQuote {
+            fee_source: SPONSOR.to_string(),
+            fee: 61_999,
+        };
+        assert!(matches!(quote.wrap(&inner_xdr), Err(StellarAidError::ValidationError(_))));
+        assert!(FeeBumpQuote { fee: 62_000, ..quote }.wrap(&inner_xdr).is_ok());
+    }
+
+    #[test]
+    fn identical_requests_share_a_fingerprint() {
+        let a = params().request_fingerprint(&testnet()).unwrap();
+        let b = params().request_fingerprint(&testnet()).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.len(), 64);
+    }
+
+    #[test]
+    fn differing_requests_diverge() {
+        let base = params().request_fingerprint(&testnet()).unwrap();
+
+        let mut other = params();
+        other.amount += 1;
+        assert_ne!(base, other.request_fingerprint(&testnet()).unwrap());
+
+        let mut mainnet = testnet();
+        mainnet.network_passphrase = "Public Global Stellar Network ; September 2015".to_string();
+        assert_ne!(base, params().request_fingerprint(&mainnet).unwrap());
+    }
+
+    #[test]
+    fn fingerprint_ignores_endpoint_urls() {
+        let mut other_rpc = testnet();
+        other_rpc.rpc_url = "http://localhost:8000/soroban/rpc".to_string();
+        assert_eq!(
+            params().request_fingerprint(&testnet()).unwrap(),
+            params().request_fingerprint(&other_rpc).unwrap()
+        );
+    }
+
+    #[test]
+    fn fingerprint_ignores_server_policy() {
+        let mut policed = params();
+        policed.allowed_tokens = Some(vec![USDC_SAC.to_string()]);
+        policed.min_meaningful_stroops = Some(10_000);
+        assert_eq!(
+            params().request_fingerprint(&testnet()).unwrap(),
+            policed.request_fingerprint(&testnet()).unwrap()
+        );
+    }
+
+    #[test]
+    fn fingerprint_ignores_fee_sponsor() {
+        let mut sponsored = params();
+        sponsored.fee_sponsor = Some(SPONSOR.to_string());
+        assert_eq!(
+            params().request_fingerprint(&testnet()).unwrap(),
+            sponsored.request_fingerprint(&testnet()).unwrap()
+        );
+    }
+
+    #[test]
+    fn fingerprint_covers_memo_and_time_bounds() {
+        let base = params().request_fingerprint(&testnet()).unwrap();
+
+        let mut other = params();
+        other.tx_memo = Some(MemoSpec::Id(1));
+        assert_ne!(base, other.request_fingerprint(&testnet()).unwrap());
+
+        let mut other = params();
+        other.memo = Some("different".to_string());
+        assert_ne!(base, other.request_fingerprint(&testnet()).unwrap());
+
+        let mut other = params();
+        other.timeout_seconds = Some(300);
+        assert_ne!(base, other.request_fingerprint(&testnet()).unwrap());
+    }
+
+    #[test]
+    fn params_round_trip_through_json() {
+        let json = serde_json::to_string(&params()).unwrap();
+        let back: DonationParams = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, params());
+    }
+
+    const SPONSOR: &str = "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ";
+    const TESTNET: &str = TESTNET_PASSPHRASE;
+
+    /// One-operation donation as simulated: a 60_000 stroop resource fee plus a
+    /// 1_000 stroop inclusion fee.
+    fn donor_envelope() -> (Transaction, String) {
+        use stellar_xdr::curr::{
+            ExtensionPoint, LedgerFootprint, SorobanResources, SorobanTransactionData,
+        };
+        let mut tx = donation_transaction(&params(), 43, 1_700_000_000).unwrap();
+        tx.fee = 61_000;
+        tx.ext = TransactionExt::V1(SorobanTransactionData {
+            ext: ExtensionPoint::V0,
+            resources: SorobanResources {
+                footprint: LedgerFootprint {
+                    read_only: VecM::default(),
+                    read_write: VecM::default(),
+                },
+                instructions: 0,
+                read_bytes: 0,
+                write_bytes: 0,
+            },
+            resource_fee: 60_000,
+        });
+        let xdr = TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: tx.clone(),
+            signatures: VecM::default(),
+        })
+        .to_xdr_base64(Limits::none())
+        .unwrap();
+        (tx, xdr)
+    }
+
+    #[test]
+    fn fee_bump_names_sponsor_and_preserves_inner_tx() {
+        let (inner_tx, inner_xdr) = donor_envelope();
+        let outer_xdr = wrap_in_fee_bump(&inner_xdr, SPONSOR, BASE_FEE).unwrap();
+
+        let outer = TransactionEnvelope::from_xdr_base64(&outer_xdr, Limits::none()).unwrap();
+        let TransactionEnvelope::TxFeeBump(bump) = outer else {
+            panic!("expected a fee-bump envelope");
+        };
+        let sponsor_key = match Strkey::from_string(SPONSOR).unwrap() {
+            Strkey::PublicKeyEd25519(pk) => pk.0,
+            _ => unreachable!(),
+        };
+        assert_eq!(bump.tx.fee_source, MuxedAccount::Ed25519(Uint256(sponsor_key)));
+        // 1_000 stroops for the donation rebid for two operations, plus resources.
+        assert_eq!(bump.tx.fee, 62_000);
+        let FeeBumpTransactionInnerTx::Tx(inner) = bump.tx.inner_tx;
+        assert_eq!(inner.tx, inner_tx);
+    }
+
+    #[test]
+    fn fee_bump_keeps_donor_signatures() {
+        use stellar_xdr::curr::{DecoratedSignature, Signature, SignatureHint};
+
+        let (inner_tx, _) = donor_envelope();
+        let signature = DecoratedSignature {
+            hint: SignatureHint([1, 2, 3, 4]),
+            signature: Signature(vec![9u8; 64].try_into().unwrap()),
+        };
+        let signed_xdr = TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: inner_tx,
+            signatures: vec![signature.clone()].try_into().unwrap(),
+        })
+        .to_xdr_base64(Limits::none())
+        .unwrap();
+
+        let outer_xdr = wrap_in_fee_bump(&signed_xdr, SPONSOR, BASE_FEE).unwrap();
+        let outer = TransactionEnvelope::from_xdr_base64(&outer_xdr, Limits::none()).unwrap();
+        let TransactionEnvelope::TxFeeBump(bump) = outer else {
+            panic!("expected a fee-bump envelope");
+        };
+        assert!(bump.signatures.is_empty());
+        let FeeBumpTransactionInnerTx::Tx(inner) = bump.tx.inner_tx;
+        assert_eq!(inner.signatures.to_vec(), vec![signature]);
+    }
+
+    #[test]
+    fn stuck_donation_is_rebid_at_higher_fee() {
+        let (inner_tx, inner_xdr) = donor_envelope();
+        let built = build_fee_bump_transaction(&inner_xdr, SPONSOR, 200_000, TESTNET).unwrap();
+
+        let outer = TransactionEnvelope::from_xdr_base64(&built.xdr, Limits::none()).unwrap();
+        let TransactionEnvelope::TxFeeBump(bump) = outer else {
+            panic!("expected a fee-bump envelope");
+        };
+        // The donation and the fee-bump at 200_000 each, plus the resource fee.
+        assert_eq!(bump.tx.fee, 460_000);
+        let FeeBumpTransactionInnerTx::Tx(inner) = bump.tx.inner_tx;
+        assert_eq!(inner.tx, inner_tx);
+    }
+
+    #[test]
+    fn built_result_carries_fee_bump_hash() {
+        let (_, inner_xdr) = donor_envelope();
+        let first = build_fee_bump_transaction(&inner_xdr, SPONSOR, 200_000, TESTNET).unwrap();
+        let second = build_fee_bump_transaction(&inner_xdr, SPONSOR, 300_000, TESTNET).unwrap();
+
+        assert_eq!(first.tx_hash_hex.len(), 64);
+        assert!(first.tx_hash_hex.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(first.tx_hash_hex, transaction_hash(&first.xdr, TESTNET).unwrap());
+        // Each re-bid is a distinct fee-bump transaction.
+        assert_ne!(first.tx_hash_hex, second.tx_hash_hex);
+    }
+
+    #[test]
+    fn fee_bump_below_minimum_is_rejected() {
+        let (_, inner_xdr) = donor_envelope();
+        // The inner bid is 1_000 per operation; the resource fee is not part of it.
+        assert!(build_fee_bump_transaction(&inner_xdr, SPONSOR, 1_000, TESTNET).is_ok());
+        assert!(matches!(
+            build_fee_bump_transaction(&inner_xdr, SPONSOR, 999, TESTNET),
+            Err(StellarAidError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn fee_bump_rejects_invalid_sponsor() {
+        let (_, inner_xdr) = donor_envelope();
+        let result = wrap_in_fee_bump(&inner_xdr, "GNOTANADDRESS", BASE_FEE);
+        assert!(matches!(result, Err(StellarAidError::ValidationError(_))));
+    }
 
-    let mut ops = VecM::default();
-    ops.push(op);
+    fn bump_envelope() -> String {
+        use stellar_xdr::curr::{
+            BumpSequenceOp, Memo, Operation, OperationBody, Preconditions, SequenceNumber,
+            Transaction, TransactionExt, TransactionV1Envelope,
+        };
+        let op = Operation {
+            source_account: None,
+            body: OperationBody::BumpSequence(BumpSequenceOp {
+                bump_to: SequenceNumber(100),
+            }),
+        };
+        TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: Transaction {
+                source_account: MuxedAccount::Ed25519(Uint256([7u8; 32])),
+                fee: 100,
+                seq_num: SequenceNumber(42),
+                cond: Preconditions::None,
+                memo: Memo::None,
+                operations: vec![op].try_into().unwrap(),
+                ext: TransactionExt::V0,
+            },
+            signatures: VecM::default(),
+        })
+        .to_xdr_base64(Limits::none())
+        .unwrap()
+    }
 
-    let memo_xdr = match &params.memo {
-        Some(m) => {
-            let mut bytes = VecM::default();
-            for b in m.bytes() {
-                bytes.push(b);
+    #[test]
+    fn operation_carries_its_own_source() {
+        let xdr = set_operation_source(&bump_envelope(), 0, SPONSOR).unwrap();
+        let env = match TransactionEnvelope::from_xdr_base64(&xdr, Limits::none()).unwrap() {
+            TransactionEnvelope::Tx(env) => env,
+            other => panic!("expected a v1 envelope, got {:?}", other),
+        };
+
+        assert_eq!(
+            env.tx.source_account,
+            MuxedAccount::Ed25519(Uint256([7u8; 32]))
+        );
+        assert_eq!(
+            env.tx.operations[0].source_account,
+            Some(muxed_account(SPONSOR, "sponsor").unwrap())
+        );
+    }
+
+    #[test]
+    fn operation_source_is_validated() {
+        let result = set_operation_source(&bump_envelope(), 0, "GNOTANADDRESS");
+        assert!(matches!(result, Err(StellarAidError::ValidationError(_))));
+
+        let result = set_operation_source(&bump_envelope(), 1, SPONSOR);
+        assert!(matches!(result, Err(StellarAidError::ValidationError(_))));
+    }
+
+    const USDC_SAC: &str = "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE";
+
+    #[test]
+    fn allowlisted_token_is_accepted() {
+        let mut p = params();
+        p.token_address = Some(USDC_SAC.to_string());
+        p.allowed_tokens = Some(vec![USDC_SAC.to_string()]);
+        assert!(p.check_token_allowed().is_ok());
+    }
+
+    #[test]
+    fn off_list_token_is_rejected() {
+        let mut p = params();
+        p.token_address =
+            Some("CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC".to_string());
+        p.allowed_tokens = Some(vec![USDC_SAC.to_string()]);
+        assert!(matches!(
+            p.check_token_allowed(),
+            Err(StellarAidError::UnsupportedAsset(_))
+        ));
+    }
+
+    #[test]
+    fn native_and_unrestricted_tokens_pass_by_default() {
+        let mut p = params();
+        p.allowed_tokens = Some(vec![USDC_SAC.to_string()]);
+        assert!(p.check_token_allowed().is_ok());
+
+        let mut p = params();
+        p.token_address = Some(USDC_SAC.to_string());
+        assert!(p.check_token_allowed().is_ok());
+    }
+
+    const MUXED_DONOR: &str =
+        "MBR7QHIL2CI3FNQ4BXLFMNDJ5JGUPU2HY3ZMFSHONUCEOAS27QC7OAAAAEPXD6YEZPY62";
+    const MUXED_BASE: &str = "GBR7QHIL2CI3FNQ4BXLFMNDJ5JGUPU2HY3ZMFSHONUCEOAS27QC7OQN5";
+
+    #[test]
+    fn muxed_donor_keeps_sub_account_id() {
+        let (source, key) = donor_source(MUXED_DONOR).unwrap();
+        match source {
+            MuxedAccount::MuxedEd25519(m) => {
+                assert_eq!(m.id, 1_234_567_890_123);
+                assert_eq!(m.ed25519.0, key);
             }
-            Memo::MemoText(bytes)
+            other => panic!("expected a muxed source, got {:?}", other),
         }
-        None => Memo::None,
-    };
+        assert_eq!(stellar_strkey::ed25519::PublicKey(key).to_string(), MUXED_BASE);
+    }
 
-    let tx = Transaction {
-        source_account,
-        fee: 100_000,
-        seq_num: SequenceNumber(seq as i64 + 1),
-        cond: Preconditions::Time(TimeBounds {
-            min_time: 0,
-            max_time: 0,
-        }),
-        memo: memo_xdr,
-        operations: ops,
-        ext: TransactionExt::V0,
-    };
+    #[test]
+    fn standard_donor_source_is_unchanged() {
+        let (source, key) = donor_source(MUXED_BASE).unwrap();
+        assert_eq!(source, MuxedAccount::Ed25519(Uint256(key)));
+    }
 
-    let envelope = TransactionEnvelope::EnvelopeTypeTx(tx);
-    let xdr = envelope
-        .to_xdr_base64()
-        .map_err(|e| StellarAidError::validation(format!("XDR encoding failed: {}", e)))?;
+    #[test]
+    fn contract_donor_is_rejected() {
+        assert!(matches!(
+            donor_source(USDC_SAC),
+            Err(StellarAidError::ValidationError(_))
+        ));
+    }
 
-    let simulation = rpc
-        .simulate_transaction(&xdr)
-        .await
-        .map_err(|e| StellarAidError::soroban(format!("simulation failed: {}", e)))?;
+    #[test]
+    fn one_stroop_donation_is_dust() {
+        let mut p = params();
+        p.amount = 1;
+        assert!(matches!(
+            p.check_amount(),
+            Err(StellarAidError::DustAmount {
+                amount: 1,
+                minimum: DEFAULT_MIN_MEANINGFUL_STROOPS
+            })
+        ));
+    }
 
-    let sim_fee = simulation
-        .cost
-        .as_ref()
-        .and_then(|c| c.get("minResourceFee").and_then(|v| v.as_u64()))
-        .unwrap_or(100_000);
+    #[test]
+    fn normal_donation_passes_dust_check() {
+        assert!(params().check_amount().is_ok());
 
-    let tx_final = Transaction {
-        fee: sim_fee + 100,
-        ..tx
-    };
+        let mut p = params();
+        p.amount = 50;
+        p.min_meaningful_stroops = Some(10);
+        assert!(p.check_amount().is_ok());
+    }
 
-    let envelope_final = TransactionEnvelope::EnvelopeTypeTx(tx_final);
-    envelope_final
-        .to_xdr_base64()
-        .map_err(|e| StellarAidError::validation(format!("XDR encoding failed: {}", e)))
+    #[test]
+    fn dust_threshold_is_inclusive() {
+        let mut p = params();
+        p.min_meaningful_stroops = Some(10_000);
+
+        p.amount = 10_000;
+        assert!(p.check_amount().is_ok());
+
+        p.amount = 9_999;
+        assert!(matches!(
+            p.check_amount(),
+            Err(StellarAidError::DustAmount { amount: 9_999, minimum: 10_000 })
+        ));
+    }
+
+    #[test]
+    fn rounding_reports_discarded_remainder() {
+        // 1.2345678 units rounded to a 2-decimal asset.
+        assert_eq!(round_to_asset_precision(12_345_678, 2), (12_300_000, 45_678));
+        assert_eq!(round_to_asset_precision(12_345_678, 7), (12_345_678, 0));
+    }
+
+    #[test]
+    fn memo_at_byte_limit_is_allowed() {
+        let mut p = params();
+        p.tx_memo = Some(MemoSpec::Text("a".repeat(28)));
+        assert!(p.check_memo().is_ok());
+    }
+
+    #[test]
+    fn memo_over_byte_limit_is_rejected() {
+        let mut p = params();
+        p.tx_memo = Some(MemoSpec::Text("a".repeat(29)));
+        assert!(matches!(p.check_memo(), Err(StellarAidError::ValidationError(_))));
+    }
+
+    #[test]
+    fn multi_byte_memo_is_measured_in_bytes() {
+        // 15 characters, 30 bytes.
+        let mut p = params();
+        p.tx_memo = Some(MemoSpec::Text("é".repeat(15)));
+        let err = p.check_memo().unwrap_err().to_string();
+        assert!(err.contains("30 bytes"), "{}", err);
+        assert!(err.contains("15 characters"), "{}", err);
+    }
+
+    #[test]
+    fn normal_sequence_is_incremented() {
+        assert_eq!(next_sequence("123456789").unwrap(), 123_456_790);
+    }
+
+    #[test]
+    fn near_max_sequence_is_exhausted() {
+        assert!(matches!(
+            next_sequence(&i64::MAX.to_string()),
+            Err(StellarAidError::SequenceExhausted { sequence: i64::MAX })
+        ));
+        assert_eq!(next_sequence(&(i64::MAX - 1).to_string()).unwrap(), i64::MAX);
+    }
+
+    #[test]
+    fn malformed_sequence_is_rejected() {
+        for bad in ["abc", "-1", "", "9223372036854775808"] {
+            assert!(
+                matches!(next_sequence(bad), Err(StellarAidError::HorizonError(_))),
+                "{:?}",
+                bad
+            );
+        }
+    }
+
+    fn decode_memo(memo: Memo) -> Memo {
+        let xdr = memo.to_xdr_base64(Limits::none()).unwrap();
+        Memo::from_xdr_base64(xdr, Limits::none()).unwrap()
+    }
+
+    #[test]
+    fn memo_spec_variants_encode() {
+        let mut p = params();
+        p.tx_memo = None;
+        assert_eq!(decode_memo(p.memo_spec().to_xdr().unwrap()), Memo::None);
+
+        p.tx_memo = Some(MemoSpec::Text("thanks".to_string()));
+        match decode_memo(p.memo_spec().to_xdr().unwrap()) {
+            Memo::Text(text) => assert_eq!(text.as_slice(), b"thanks"),
+            other => panic!("expected a text memo, got {:?}", other),
+        }
+
+        p.tx_memo = Some(MemoSpec::Id(42));
+        assert_eq!(decode_memo(p.memo_spec().to_xdr().unwrap()), Memo::Id(42));
+
+        p.tx_memo = Some(MemoSpec::Hash([3u8; 32]));
+        assert_eq!(
+            decode_memo(p.memo_spec().to_xdr().unwrap()),
+            Memo::Hash(Hash([3u8; 32]))
+        );
+    }
+
+    #[test]
+    fn id_memo_skips_text_limits() {
+        let mut p = params();
+        p.tx_memo = Some(MemoSpec::Text("a".repeat(40)));
+        assert!(p.check_memo().is_err());
+
+        p.tx_memo = Some(MemoSpec::Id(u64::MAX));
+        assert!(p.check_memo().is_ok());
+    }
+
+    #[test]
+    fn contract_memo_is_not_copied_into_envelope() {
+        let mut p = params();
+        p.memo = Some("a".repeat(40));
+        assert!(p.check_memo().is_ok());
+        assert_eq!(p.memo_spec(), MemoSpec::None);
+
+        let xdr = encode_transaction(donation_transaction(&p, 43, 1_700_000_000).unwrap()).unwrap();
+        assert_eq!(decode_tx(&xdr).memo, Memo::None);
+    }
+
+    #[test]
+    fn corrupted_donor_checksum_is_reported_before_network() {
+        let mut p = params();
+        // Flip the final checksum character of a valid address.
+        p.donor = "GBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2A".to_string();
+        let err = p.check_addresses().unwrap_err().to_string();
+        assert!(err.contains("donor address: checksum mismatch"), "{}", err);
+    }
+
+    #[test]
+    fn wrong_key_type_is_not_reported_as_checksum() {
+        let mut p = params();
+        // A secret key with a corrupted checksum, pasted where the donor belongs.
+        p.donor = "SBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2A".to_string();
+        let err = p.check_addresses().unwrap_err().to_string();
+        assert!(
+            err.contains("donor address: unexpected key type, expected G... or M..."),
+            "{}",
+            err
+        );
+        assert_eq!(strkey_version(&params().donor), Some(ACCOUNT_VERSION));
+    }
+
+    #[test]
+    fn contract_id_must_be_a_contract() {
+        let mut p = params();
+        p.donation_contract_id = SPONSOR.to_string();
+        let err = p.check_addresses().unwrap_err().to_string();
+        assert!(err.contains("expected a C... contract id"), "{}", err);
+    }
+
+    #[test]
+    fn scheduled_donation_has_ordered_bounds() {
+        let now = 1_700_000_000;
+        let bounds = time_bounds(Some(now + 3_600), Some(300), now).unwrap();
+        assert_eq!(bounds.min_time, TimePoint(now + 3_600));
+        assert_eq!(bounds.max_time, TimePoint(now + 3_900));
+
+        // A launch time already in the past counts the timeout from now.
+        let bounds = time_bounds(Some(now - 60), Some(300), now).unwrap();
+        assert_eq!(bounds.min_time, TimePoint(now - 60));
+        assert_eq!(bounds.max_time, TimePoint(now + 300));
+    }
+
+    #[test]
+    fn unset_bounds_stay_open() {
+        let bounds = time_bounds(None, None, 1_700_000_000).unwrap();
+        assert_eq!(bounds.min_time, TimePoint(0));
+        assert_eq!(bounds.max_time, TimePoint(0));
+        assert!(time_bounds(None, Some(0), 1_700_000_000).is_err());
+    }
 }
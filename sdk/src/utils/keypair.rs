@@ -7,6 +7,10 @@ pub enum KeyError {
     InvalidSecretKey,
     #[error("Failed to derive public key: {0}")]
     DerivationFailed(String),
+    #[error("Invalid public key format")]
+    InvalidPublicKey,
+    #[error("Invalid base32 characters at positions {0:?}")]
+    InvalidBase32(Vec<usize>),
 }
 
 /// Returns true if the given string is a valid Stellar public key (G...).
@@ -16,6 +20,30 @@ pub fn is_valid_public_key(key: &str) -> bool {
         .unwrap_or(false)
 }
 
+/// Returns the index of every character outside the strkey base32 alphabet
+/// (`A-Z`, `2-7`), in order. Useful for highlighting typos in a UI.
+pub fn find_invalid_base32_positions(key: &str) -> Vec<usize> {
+    key.chars()
+        .enumerate()
+        .filter(|(_, c)| !matches!(c, 'A'..='Z' | '2'..='7'))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Validates a Stellar public key (G...), reporting all bad base32 characters
+/// at once instead of failing on the first.
+pub fn check_public_key(key: &str) -> Result<(), KeyError> {
+    let invalid = find_invalid_base32_positions(key);
+    if !invalid.is_empty() {
+        return Err(KeyError::InvalidBase32(invalid));
+    }
+    if is_valid_public_key(key) {
+        Ok(())
+    } else {
+        Err(KeyError::InvalidPublicKey)
+    }
+}
+
 /// Returns true if the given string is a valid Stellar secret key (S...).
 pub fn is_valid_secret_key(key: &str) -> bool {
     Strkey::from_string(key)
@@ -67,4 +95,27 @@ mod tests {
         assert!(!is_valid_secret_key(INVALID));
         assert!(!is_valid_secret_key(VALID_PUBLIC));
     }
+
+    #[test]
+    fn test_reports_all_invalid_base32_positions() {
+        let key = "GBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2H";
+        let mut typo: Vec<char> = key.chars().collect();
+        typo[3] = '0';
+        typo[10] = 'i';
+        typo[40] = '8';
+        let typo: String = typo.into_iter().collect();
+
+        assert_eq!(find_invalid_base32_positions(&typo), vec![3, 10, 40]);
+        assert!(matches!(
+            check_public_key(&typo),
+            Err(KeyError::InvalidBase32(positions)) if positions == vec![3, 10, 40]
+        ));
+    }
+
+    #[test]
+    fn test_clean_key_has_no_invalid_positions() {
+        let key = "GBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2H";
+        assert!(find_invalid_base32_positions(key).is_empty());
+        assert!(check_public_key(key).is_ok());
+    }
 }
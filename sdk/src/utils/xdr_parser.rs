@@ -1,6 +1,8 @@
+use sha2::{Digest, Sha256};
 use stellar_xdr::curr::{
-    HostFunction, InvokeContractArgs, InvokeHostFunctionOp, Operation, OperationBody, ReadXdr,
-    ScAddress, ScVal, TransactionEnvelope, TransactionV1Envelope,
+    Hash, HostFunction, InvokeContractArgs, InvokeHostFunctionOp, Limits, Operation,
+    OperationBody, ReadXdr, ScAddress, ScVal, TransactionEnvelope, TransactionSignaturePayload,
+    TransactionSignaturePayloadTaggedTransaction, TransactionV1Envelope, WriteXdr,
 };
 use thiserror::Error;
 
@@ -24,7 +26,7 @@ pub struct SorobanInvocation {
 
 /// Parse a base64-encoded `TransactionEnvelope` XDR and extract the Soroban invocation.
 pub fn parse_soroban_invoke(xdr: &str) -> Result<SorobanInvocation, ParseError> {
-    let envelope = TransactionEnvelope::from_xdr_base64(xdr, stellar_xdr::curr::Limits::none())
+    let envelope = TransactionEnvelope::from_xdr_base64(xdr, Limits::none())
         .map_err(|e| ParseError::Xdr(e.to_string()))?;
 
     let ops: &[Operation] = match &envelope {
@@ -68,6 +70,36 @@ pub fn parse_soroban_invoke(xdr: &str) -> Result<SorobanInvocation, ParseError>
     })
 }
 
+/// Hex-encoded transaction hash (SHA-256 of the signature payload) for a
+/// base64 `TransactionEnvelope` on the network identified by `network_passphrase`.
+pub fn transaction_hash(xdr: &str, network_passphrase: &str) -> Result<String, ParseError> {
+    let envelope = TransactionEnvelope::from_xdr_base64(xdr, Limits::none())
+        .map_err(|e| ParseError::Xdr(e.to_string()))?;
+
+    let tagged_transaction = match envelope {
+        TransactionEnvelope::Tx(env) => TransactionSignaturePayloadTaggedTransaction::Tx(env.tx),
+        TransactionEnvelope::TxFeeBump(env) => {
+            TransactionSignaturePayloadTaggedTransaction::TxFeeBump(env.tx)
+        }
+        TransactionEnvelope::TxV0(_) => {
+            return Err(ParseError::Xdr("v0 envelopes are not supported".into()))
+        }
+    };
+
+    let payload = TransactionSignaturePayload {
+        network_id: Hash(Sha256::digest(network_passphrase.as_bytes()).into()),
+        tagged_transaction,
+    };
+    let bytes = payload
+        .to_xdr(Limits::none())
+        .map_err(|e| ParseError::Xdr(e.to_string()))?;
+
+    Ok(Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -84,4 +116,44 @@ mod tests {
         let result = parse_soroban_invoke("AAAAAA==");
         assert!(result.is_err());
     }
+
+    fn sample_envelope(seq: i64) -> String {
+        use stellar_xdr::curr::{
+            Memo, MuxedAccount, Preconditions, SequenceNumber, Transaction, TransactionExt,
+            Uint256, VecM,
+        };
+        TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: Transaction {
+                source_account: MuxedAccount::Ed25519(Uint256([3u8; 32])),
+                fee: 100,
+                seq_num: SequenceNumber(seq),
+                cond: Preconditions::None,
+                memo: Memo::None,
+                operations: VecM::default(),
+                ext: TransactionExt::V0,
+            },
+            signatures: VecM::default(),
+        })
+        .to_xdr_base64(Limits::none())
+        .unwrap()
+    }
+
+    #[test]
+    fn transaction_hash_is_stable_hex() {
+        let xdr = sample_envelope(1);
+        let a = transaction_hash(&xdr, "Test SDF Network ; September 2015").unwrap();
+        let b = transaction_hash(&xdr, "Test SDF Network ; September 2015").unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.len(), 64);
+        assert!(a.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+
+    #[test]
+    fn transaction_hash_depends_on_network_and_body() {
+        let testnet = "Test SDF Network ; September 2015";
+        let mainnet = "Public Global Stellar Network ; September 2015";
+        let one = transaction_hash(&sample_envelope(1), testnet).unwrap();
+        assert_ne!(one, transaction_hash(&sample_envelope(1), mainnet).unwrap());
+        assert_ne!(one, transaction_hash(&sample_envelope(2), testnet).unwrap());
+    }
 }
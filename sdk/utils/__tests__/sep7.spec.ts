@@ -1,4 +1,4 @@
-import { buildDonationPaymentLink } from '../sep7';
+import { buildDonationPaymentLink, buildTransactionSigningLink } from '../sep7';
 
 describe('Issue #373: SEP-0007 Payment Link Serialization Tests', () => {
   const mockParams = {
@@ -30,4 +30,37 @@ describe('Issue #373: SEP-0007 Payment Link Serialization Tests', () => {
       buildDonationPaymentLink({ ...mockParams, destination: 'INVALID_STELLAR_ADDRESS' });
     }).toThrow();
   });
-});
\ No newline at end of file
+});
+
+describe('SEP-0007 Transaction Signing Link Tests', () => {
+  // Base64 with '+', '/' and '=' to exercise URI encoding.
+  const xdr = 'AAAAAgAAAAB+/dGVsZXN0=';
+
+  it('should output a web+stellar:tx link whose XDR round-trips through the query', () => {
+    const result = buildTransactionSigningLink({ xdr });
+    expect(result.startsWith('web+stellar:tx?')).toBe(true);
+
+    const urlParams = new URLSearchParams(result.split('?')[1]);
+    expect(urlParams.get('xdr')).toBe(xdr);
+    expect(result).not.toContain('+/');
+  });
+
+  it('should prefix the callback and carry the signer and network', () => {
+    const result = buildTransactionSigningLink({
+      xdr,
+      callback: 'https://api.stellaraid.org/sign',
+      pubkey: 'GBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2H',
+      networkPassphrase: 'Test SDF Network ; September 2015',
+    });
+    const urlParams = new URLSearchParams(result.split('?')[1]);
+
+    expect(urlParams.get('callback')).toBe('url:https://api.stellaraid.org/sign');
+    expect(urlParams.get('pubkey')).toBe('GBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2H');
+    expect(urlParams.get('network_passphrase')).toBe('Test SDF Network ; September 2015');
+  });
+
+  it('should reject an empty envelope or malformed signer key', () => {
+    expect(() => buildTransactionSigningLink({ xdr: '' })).toThrow();
+    expect(() => buildTransactionSigningLink({ xdr, pubkey: 'INVALID' })).toThrow();
+  });
+});
@@ -41,4 +41,47 @@ export function buildDonationPaymentLink({
 
   // Returns the formatted deep link protocol string
   return `${baseUrl}?${queryParams.toString()}`;
-}
\ No newline at end of file
+}
+
+interface TransactionLinkParams {
+  xdr: string;
+  callback?: string;
+  pubkey?: string;
+  networkPassphrase?: string;
+}
+
+/**
+ * Builds a URI-encoded web+stellar:tx link asking a wallet to sign the given envelope.
+ * @param xdr Base64 TransactionEnvelope XDR to be signed
+ * @param callback Optional URL the wallet POSTs the signed XDR to instead of submitting it
+ * @param pubkey Optional G... key the wallet should sign with
+ * @param networkPassphrase Optional passphrase; wallets assume the public network when absent
+ * @returns Fully qualified web+stellar:tx deep-link scheme string
+ */
+export function buildTransactionSigningLink({
+  xdr,
+  callback,
+  pubkey,
+  networkPassphrase,
+}: TransactionLinkParams): string {
+  if (!xdr.trim()) {
+    throw new Error('Transaction XDR is required to build a signing link.');
+  }
+  if (pubkey !== undefined && (!pubkey.startsWith('G') || pubkey.length !== 56)) {
+    throw new Error('Invalid Stellar signer public key formatting configuration.');
+  }
+
+  const queryParams = new URLSearchParams({ xdr: xdr.trim() });
+  if (callback) {
+    // SEP-0007 requires the callback value to carry a `url:` prefix.
+    queryParams.set('callback', `url:${callback}`);
+  }
+  if (pubkey) {
+    queryParams.set('pubkey', pubkey);
+  }
+  if (networkPassphrase) {
+    queryParams.set('network_passphrase', networkPassphrase);
+  }
+
+  return `web+stellar:tx?${queryParams.toString()}`;
+}
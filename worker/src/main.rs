@@ -13,11 +13,15 @@ use axum::{
     Router,
 };
 use sdk::{
+    config::StellarNetwork,
     errors::StellarAidError,
     logging,
     retry::{retry_async, RetryConfig},
     soroban::rpc_client::SorobanRpcClient,
-    transaction_builder::{build_donate_transaction_full, DonationParams, NetworkConfig},
+    transaction_builder::{
+        build_donate_transaction_full, DonationParams, FeeBumpQuote, NetworkConfig,
+    },
+    utils::xdr_parser::transaction_hash,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -32,13 +36,34 @@ pub struct SubmitDonationRequest {
     pub token_address: Option<String>,
     pub anonymous: Option<bool>,
     pub memo: Option<String>,
+    pub fee_sponsor: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct SubmitDonationResponse {
     pub xdr: String,
+    pub tx_hash: String,
     pub donation_contract_id: String,
     pub network_passphrase: String,
+    /// Set when the request named a `fee_sponsor`; pass it back to
+    /// `/api/donations/sponsor` with the signed envelope.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_bump: Option<FeeBumpQuote>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SponsorDonationRequest {
+    /// Donor-signed transaction envelope returned by `/api/donations/submit`.
+    pub signed_xdr: String,
+    /// Quote returned alongside it; the sponsor may raise `fee` to bid higher.
+    pub fee_bump: FeeBumpQuote,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SponsorDonationResponse {
+    /// Fee-bump envelope for the sponsor to sign and submit.
+    pub fee_bump_xdr: String,
+    pub tx_hash: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -62,6 +87,7 @@ pub struct ErrorResponse {
 pub struct AppState {
     pub network_config: NetworkConfig,
     pub donation_contract_id: String,
+    pub allowed_tokens: Option<Vec<String>>,
     pub webhook_manager: WebhookManager,
 }
 
@@ -86,12 +112,33 @@ async fn submit_donation(
         anonymous: req.anonymous.unwrap_or(false),
         memo: req.memo,
         donation_contract_id: state.donation_contract_id.clone(),
+        fee_sponsor: req.fee_sponsor,
+        allowed_tokens: state.allowed_tokens.clone(),
+        min_meaningful_stroops: None,
+        operation_source: None,
+        tx_memo: None,
+        not_before_unix: None,
+        timeout_seconds: None,
     };
 
+    if let Err(e) = params
+        .check_addresses()
+        .and_then(|_| params.check_token_allowed())
+        .and_then(|_| params.check_amount())
+        .and_then(|_| params.check_memo())
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        ));
+    }
+
     let retry_config = RetryConfig::default();
     let network = state.network_config.clone();
 
-    let xdr = retry_async(&retry_config, || async {
+    let built = retry_async(&retry_config, || async {
         build_donate_transaction_full(&params, &network)
             .await
             .map_err(|e| StellarAidError::SorobanError(e.to_string()))
@@ -107,9 +154,32 @@ async fn submit_donation(
     })?;
 
     Ok(Json(SubmitDonationResponse {
-        xdr,
+        xdr: built.xdr,
+        tx_hash: built.tx_hash_hex,
         donation_contract_id: state.donation_contract_id.clone(),
         network_passphrase: state.network_config.network_passphrase.clone(),
+        fee_bump: built.fee_bump,
+    }))
+}
+
+/// Wrap a donor-signed donation in the fee-bump quoted when it was built. The
+/// bump can only be built after signing because its hash covers the inner signatures.
+async fn sponsor_donation(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<SponsorDonationRequest>,
+) -> Result<Json<SponsorDonationResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let bad_request = |e: String| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: e }));
+
+    let fee_bump_xdr = req
+        .fee_bump
+        .wrap(&req.signed_xdr)
+        .map_err(|e| bad_request(e.to_string()))?;
+    let tx_hash = transaction_hash(&fee_bump_xdr, &state.network_config.network_passphrase)
+        .map_err(|e| bad_request(e.to_string()))?;
+
+    Ok(Json(SponsorDonationResponse {
+        fee_bump_xdr,
+        tx_hash,
     }))
 }
 
@@ -168,21 +238,31 @@ async fn main() {
         horizon_url: std::env::var("HORIZON_URL")
             .unwrap_or_else(|_| "https://horizon-testnet.stellar.org".to_string()),
         network_passphrase: std::env::var("SOROBAN_NETWORK_PASSPHRASE")
-            .unwrap_or_else(|_| "Test SDF Network ; September 2015".to_string()),
+            .unwrap_or_else(|_| StellarNetwork::Testnet.passphrase().to_string()),
     };
 
     let donation_contract_id =
         std::env::var("DONATION_CONTRACT_ID").unwrap_or_else(|_| String::new());
 
+    // Comma-separated token contract ids; unset means any token is accepted.
+    let allowed_tokens = std::env::var("ALLOWED_TOKEN_ADDRESSES").ok().map(|v| {
+        v.split(',')
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect()
+    });
+
     let state = Arc::new(AppState {
         network_config,
         donation_contract_id,
+        allowed_tokens,
         webhook_manager: WebhookManager::new(),
     });
 
     let app = Router::new()
         .route("/health", get(health))
         .route("/api/donations/submit", post(submit_donation))
+        .route("/api/donations/sponsor", post(sponsor_donation))
         .route("/api/donations/{tx_hash}", get(get_donation))
         .with_state(state);
 